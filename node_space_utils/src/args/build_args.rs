@@ -6,6 +6,8 @@ pub struct BuildArgs {
     pub watch: bool,
 
     #[arg(short, long)]
+    /// let the coordinator watch and build the project, the registration stays after the command
+    /// exits until `node-space coordinator unwatch` removes it
     pub deamon: bool,
 
     #[arg(short, long)]
@@ -13,4 +15,8 @@ pub struct BuildArgs {
 
     #[arg(short, long)]
     pub output_dir: Option<String>,
+
+    #[arg(short, long)]
+    /// build even when nothing changed since the last build of the project and its links
+    pub force: bool,
 }
@@ -0,0 +1,8 @@
+use clap::Args;
+
+#[derive(Args)]
+pub struct CacheCleanArgs {
+    #[arg(short, long)]
+    /// only forget the builds of a project, by name, alias or path, defaults to every project
+    pub project: Option<String>,
+}
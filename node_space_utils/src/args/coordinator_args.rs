@@ -1,10 +1,90 @@
 use clap::Args;
 
 #[derive(Args)]
-pub struct CoordinatorStartArgs {}
+pub struct CoordinatorStartArgs {
+    #[arg(short, long)]
+    /// max number of builds running in parallel, defaults to the number of cpus
+    pub jobs: Option<usize>,
+
+    #[arg(long)]
+    /// ignore the registrations saved by the previous coordinator and start with none
+    pub fresh: bool,
+
+    #[arg(long)]
+    /// error, warn, info or debug, defaults to `NODE_SPACE_LOG_LEVEL` or info
+    pub log_level: Option<String>,
+
+    #[arg(long)]
+    /// text or json, defaults to `NODE_SPACE_LOG_FORMAT` or text
+    pub log_format: Option<String>,
+
+    #[arg(long)]
+    /// rotate the log once it reaches this size (`512K`, `10M`), defaults to
+    /// `NODE_SPACE_LOG_MAX_SIZE` or 10M
+    pub log_max_size: Option<String>,
+
+    #[arg(long)]
+    /// number of rotated logs to keep, defaults to `NODE_SPACE_LOG_MAX_FILES` or 5
+    pub log_max_files: Option<usize>,
+}
 
 #[derive(Args)]
 pub struct CoordinatorLogArgs {
     #[arg(short, long)]
+    /// keep printing new lines as they are written
     pub watch: bool,
+
+    #[arg(short, long)]
+    /// only show lines of a project, matched against the project path or name
+    pub project: Option<String>,
+
+    #[arg(short, long)]
+    /// minimum level to show: error, warn, info or debug
+    pub level: Option<String>,
+
+    #[arg(short, long)]
+    /// only show lines newer than a duration (`30s`, `15m`, `2h`, `1d`) or an utc timestamp
+    pub since: Option<String>,
+
+    #[arg(long)]
+    /// show the log of the previous coordinator run instead of the current one
+    pub previous: bool,
+}
+
+#[derive(Args)]
+pub struct CoordinatorStatusArgs {
+    #[arg(short, long)]
+    /// print the status as json
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct CoordinatorStopArgs {
+    #[arg(short, long)]
+    /// send SIGTERM right away instead of asking the coordinator to stop
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct CoordinatorRestartArgs {
+    #[arg(short, long)]
+    /// max number of builds running in parallel, defaults to the value of the running coordinator
+    pub jobs: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct CoordinatorUnwatchArgs {
+    /// path of the project to unwatch, defaults to the current directory
+    pub path: Option<String>,
+}
+
+#[derive(Args)]
+pub struct CoordinatorListArgs {
+    #[arg(short, long)]
+    /// include instances that are not running
+    pub all: bool,
+
+    #[arg(short, long)]
+    /// print the instances as json
+    pub json: bool,
 }
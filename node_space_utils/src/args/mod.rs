@@ -1,7 +1,9 @@
 pub mod build_args;
+pub mod cache_args;
 pub mod coordinator_args;
 pub mod dependencies_args;
 pub mod group_args;
 pub mod link_args;
 pub mod project_args;
 pub mod server_args;
+pub mod unlink_args;
@@ -6,4 +6,24 @@ pub struct ProjectArgs {
 
     #[arg(short, long)]
     pub output_dir: Option<String>,
+
+    #[arg(long)]
+    /// only changes matching these globs trigger a build, can be repeated
+    pub include: Vec<String>,
+
+    #[arg(long)]
+    /// changes matching these globs never trigger a build, can be repeated
+    pub exclude: Vec<String>,
+
+    #[arg(long)]
+    /// milliseconds without changes before a build starts, defaults to 3000
+    pub debounce: Option<u64>,
+
+    #[arg(long)]
+    /// build when the watcher starts, local watchers do by default and the coordinator doesn't
+    pub build_on_start: Option<bool>,
+
+    #[arg(long)]
+    /// create, modify, rename, remove or metadata, can be repeated, defaults to all of them
+    pub trigger: Vec<String>,
 }
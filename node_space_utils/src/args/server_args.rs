@@ -6,6 +6,19 @@ pub struct StartServerArgs {
 
     #[arg(short, long)]
     pub port: Option<String>,
+
+    #[arg(long)]
+    /// don't reload open pages when the output dir of a route changes
+    pub no_live_reload: bool,
+
+    #[arg(long)]
+    /// serve over https with the cert and key of the config, or a self signed localhost
+    /// certificate stored in ~/.config/node-space/certs
+    pub tls: bool,
+
+    #[arg(long, requires = "tls")]
+    /// create a new self signed localhost certificate before starting
+    pub generate_cert: bool,
 }
 
 #[derive(Args)]
@@ -13,7 +26,7 @@ pub struct ConfigServerArgs {
     /// config name
     pub name: String,
     /// routes should have the format "route_name => project_name, route_name2 => project_name2"
-    /// project_name must exist
+    /// project_name must exist, or be an upstream url to proxy to: "/api => http://localhost:8080"
     pub routes: String,
 
     #[arg(short, long)]
@@ -24,4 +37,12 @@ pub struct ConfigServerArgs {
     /// main route to look for index.html if not specified each route will look for its own
     /// index.html
     pub main_route: Option<String>,
+
+    #[arg(long, requires = "tls_key")]
+    /// pem certificate used by `server start --tls`
+    pub tls_cert: Option<String>,
+
+    #[arg(long, requires = "tls_cert")]
+    /// pem private key of the certificate
+    pub tls_key: Option<String>,
 }
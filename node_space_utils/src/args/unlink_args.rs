@@ -0,0 +1,20 @@
+use clap::Args;
+
+#[derive(Args)]
+pub struct UnlinkArgs {
+    /// linked package name or alias, if not specified all the linked packages of the project are
+    /// unlinked
+    pub name: Option<String>,
+
+    #[arg(short, long)]
+    /// unlink every project in the group instead of the current project
+    pub group: Option<String>,
+
+    #[arg(short, long)]
+    /// unregister the current package and unlink it from every project that links to it
+    pub package: bool,
+
+    #[arg(short, long)]
+    /// install the registry version even if a backup of the original install exists
+    pub reinstall: bool,
+}
@@ -1,41 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::{
     args::build_args::BuildArgs,
+    cache::cached_build::run_cached_build,
     command_line::node_build::run_node_command,
     errors::node_space::NodeSpaceError,
-    modals::{config_file::ConfigFile, package::Package, socket_build_data::SocketBuildData},
+    modals::{
+        build_cache::BuildCache, config_file::ConfigFile, package::Package,
+        socket_build_data::SocketBuildData,
+    },
     package_utils::get_base_package_data,
     path_utils::get_current_path,
-    watch_coordinator::client::start_coordinator::{
-        add_local_project_watcher, request_build_watcher_for_project, start_coordinator,
+    watch_coordinator::client::{
+        build_events::print_build_events,
+        start_coordinator::{
+            add_local_project_watcher, request_build_watcher_for_project, start_coordinator,
+        },
     },
 };
 
+/// linked packages can have links of their own, register them as well so the coordinator knows
+/// the whole dependency chain of the project, the registrations are owned by `project`
+fn build_dependencies_socket_data(
+    config_file: &ConfigFile,
+    project: &Package,
+    symlinks: &[Package],
+) -> Vec<SocketBuildData> {
+    let mut result = Vec::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut pending = symlinks.to_vec();
+
+    while let Some(package) = pending.pop() {
+        if !visited.insert(package.path.clone()) {
+            continue;
+        }
+
+        let package_symlinks = match config_file.symlinks.get(&package.name) {
+            Some(value) => value.to_vec(),
+            None => continue,
+        };
+
+        let package_symlinks = package_symlinks
+            .iter()
+            .map(|symlink| config_file.with_watch_settings(symlink))
+            .collect::<Vec<Package>>();
+
+        pending.extend(package_symlinks.iter().cloned());
+        result.push(
+            SocketBuildData::new(
+                package_symlinks,
+                config_file.with_watch_settings(&package),
+                false,
+            )
+            .with_owner(&project.path),
+        );
+    }
+
+    result
+}
+
 pub fn handle_watch_project_with_dependencies(
     data: SocketBuildData,
+    dependencies_data: Vec<SocketBuildData>,
 ) -> Result<bool, NodeSpaceError> {
     dbg!("handle_watch_project_with_dependencies");
 
-    start_coordinator()?;
+    start_coordinator(None)?;
+
+    for dependency_data in dependencies_data.iter() {
+        request_build_watcher_for_project(dependency_data.clone())?;
+    }
+
     request_build_watcher_for_project(data.clone())?;
 
     if data.watch_only_links {
         dbg!("watch_only_links");
 
+        let packages = dependencies_data
+            .iter()
+            .flat_map(|dependency_data| dependency_data.symlinks.iter())
+            .chain(data.symlinks.iter())
+            .map(|package| (package.path.clone(), package.name.clone()))
+            .collect::<HashMap<String, String>>();
+
+        if let Err(error) = print_build_events(packages) {
+            println!("⚠️  can't show dependency builds: {}", error);
+        }
+
         add_local_project_watcher(data)?;
 
         return Ok(true);
     }
 
+    println!(
+        "👀 {} is watched by the coordinator until `node-space coordinator unwatch`",
+        data.project.name
+    );
+
     Ok(true)
 }
 
 pub fn handle_build_command(args: &BuildArgs) -> Result<bool, NodeSpaceError> {
-    if !args.watch && !args.start {
-        let path = get_current_path()?;
-
-        return Ok(run_node_command(&path, "build")?);
-    }
-
     if !args.watch && args.start {
         let path = get_current_path()?;
 
@@ -44,23 +109,45 @@ pub fn handle_build_command(args: &BuildArgs) -> Result<bool, NodeSpaceError> {
 
     let config_file = ConfigFile::new()?;
     let (_, package_name, current_path) = get_base_package_data(None)?;
-    let current_project = Package::new(
+    let current_project = config_file.with_watch_settings(&Package::new(
         current_path,
         package_name.clone(),
         None,
         args.output_dir.clone(),
-    );
+    ));
 
     let current_symlink_option = config_file.symlinks.get(&package_name);
     let is_local_watcher = !args.deamon;
 
     let effective_symlinks = match current_symlink_option {
-        Some(value) => value.to_vec(),
+        Some(value) => value
+            .iter()
+            .map(|package| config_file.with_watch_settings(package))
+            .collect(),
         None => Vec::new(),
     };
 
     let has_symlinks = !effective_symlinks.is_empty();
 
+    let dependencies_data =
+        build_dependencies_socket_data(&config_file, &current_project, &effective_symlinks);
+
+    if args.force {
+        let paths = dependencies_data
+            .iter()
+            .flat_map(|dependency_data| dependency_data.symlinks.iter())
+            .chain(effective_symlinks.iter())
+            .map(|package| package.path.clone())
+            .chain(std::iter::once(current_project.path.clone()))
+            .collect::<Vec<String>>();
+
+        BuildCache::remove(&paths)?;
+    }
+
+    if !args.watch {
+        return run_cached_build(&current_project, &effective_symlinks);
+    }
+
     dbg!("{}", &effective_symlinks);
 
     let socket_data = SocketBuildData::new(effective_symlinks, current_project, is_local_watcher);
@@ -73,5 +160,5 @@ pub fn handle_build_command(args: &BuildArgs) -> Result<bool, NodeSpaceError> {
         return Ok(true);
     }
 
-    handle_watch_project_with_dependencies(socket_data)
+    handle_watch_project_with_dependencies(socket_data, dependencies_data)
 }
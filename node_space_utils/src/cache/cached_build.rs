@@ -0,0 +1,32 @@
+use crate::{
+    command_line::node_build::run_node_command,
+    errors::node_space::NodeSpaceError,
+    modals::{build_cache::BuildCache, package::Package},
+};
+
+use super::input_hash::get_input_hash;
+
+/// `npm run build` unless nothing the build reads changed since its last successful run
+pub fn run_cached_build(
+    project: &Package,
+    dependencies: &[Package],
+) -> Result<bool, NodeSpaceError> {
+    let input_hash = get_input_hash(project, dependencies);
+
+    if BuildCache::new()?.is_up_to_date(project, &input_hash) {
+        println!(
+            "✅ {} didn't change since its last build, skipping (use --force to rebuild)",
+            project.name
+        );
+
+        return Ok(true);
+    }
+
+    run_node_command(&project.path, "build")?;
+
+    if let Err(error) = BuildCache::record(&project.path, &input_hash) {
+        println!("⚠️  can't save the build cache: {}", error);
+    }
+
+    Ok(true)
+}
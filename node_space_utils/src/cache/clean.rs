@@ -0,0 +1,57 @@
+use std::fs;
+
+use crate::{
+    args::cache_args::CacheCleanArgs,
+    errors::node_space::NodeSpaceError,
+    modals::{build_cache::BuildCache, config_file::ConfigFile},
+};
+
+/// the project paths `name` refers to, a known project name or alias, or a path
+fn get_project_paths(name: &str) -> Result<Vec<String>, NodeSpaceError> {
+    let config_file = ConfigFile::new()?;
+
+    let mut paths = config_file
+        .projects
+        .iter()
+        .chain(config_file.linked_packages.iter())
+        .filter(|package| package.is_named(name))
+        .map(|package| package.path.clone())
+        .collect::<Vec<String>>();
+
+    let path = match fs::canonicalize(name) {
+        Ok(value) => value.to_string_lossy().to_string(),
+        Err(_) => name.to_string(),
+    };
+
+    paths.push(path);
+
+    Ok(paths)
+}
+
+pub fn handle_cache_clean(args: &CacheCleanArgs) -> Result<bool, NodeSpaceError> {
+    let project = match &args.project {
+        Some(value) => value,
+        None => {
+            let cache = BuildCache::new()?;
+
+            match fs::remove_file(cache.get_cache_path()) {
+                Ok(_) => {}
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                Err(error) => return Err(NodeSpaceError::CantSaveBuildCache(error.to_string())),
+            };
+
+            println!("🧹 removed {} cached builds", cache.entries.len());
+
+            return Ok(true);
+        }
+    };
+
+    let removed_count = BuildCache::remove(&get_project_paths(project)?)?;
+
+    match removed_count {
+        0 => println!("🤷 no cached builds for {}", project),
+        _ => println!("🧹 removed the cached build of {}", project),
+    };
+
+    Ok(true)
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    modals::package::Package, watch_coordinator::runtime_dir::get_workspace_root,
+    watch_filter::WatchFilter,
+};
+
+const LOCK_FILE_NAMES: [&str; 4] = [
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+];
+
+/// fnv-1a, stable between builds so hashes saved by an older binary stay comparable
+struct InputHasher {
+    hash: u64,
+}
+
+impl InputHasher {
+    fn new() -> Self {
+        Self {
+            hash: 0xcbf29ce484222325,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash = (self.hash ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// the path is part of the hash so renaming a file changes it as well
+    fn write_file(&mut self, path: &Path) {
+        self.write(path.to_string_lossy().as_bytes());
+        self.write(&[0]);
+
+        match fs::read(path) {
+            Ok(content) => self.write(&content),
+            Err(_) => self.write(b"<unreadable>"),
+        }
+
+        self.write(&[0]);
+    }
+}
+
+/// files under `dir` without following symlinks, folders for which `is_skipped` is true are not
+/// visited
+fn collect_files<F: Fn(&Path) -> bool>(dir: &Path, is_skipped: &F, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+
+        let file_type = match entry.file_type() {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        if is_skipped(&path) {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            collect_files(&path, is_skipped, files);
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+/// the lock files of the project and of the workspace it is part of
+fn get_lock_files(project_path: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_path.to_path_buf()];
+
+    if let Some(workspace_root) = get_workspace_root(project_path) {
+        if workspace_root != project_path {
+            dirs.push(workspace_root);
+        }
+    }
+
+    dirs.iter()
+        .flat_map(|dir| LOCK_FILE_NAMES.iter().map(move |name| dir.join(name)))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// hash of everything a build of the project reads: its sources (the files a watcher would react
+/// to, package.json included), the lock files and the output folders of its linked dependencies
+pub fn get_input_hash(project: &Package, dependencies: &[Package]) -> String {
+    let filter = WatchFilter::new(project);
+    let project_path = Path::new(&project.path);
+
+    let mut files = Vec::new();
+
+    collect_files(
+        project_path,
+        &|path: &Path| match path.is_dir() {
+            true => filter.is_excluded(path),
+            false => !filter.is_relevant(path),
+        },
+        &mut files,
+    );
+
+    files.extend(get_lock_files(project_path));
+
+    for dependency in dependencies {
+        let output_path = Path::new(&dependency.path).join(&dependency.output_name);
+
+        collect_files(&output_path, &|_: &Path| false, &mut files);
+    }
+
+    files.sort();
+    files.dedup();
+
+    let mut hasher = InputHasher::new();
+
+    for file in files.iter() {
+        hasher.write_file(file);
+    }
+
+    format!("{:016x}", hasher.hash)
+}
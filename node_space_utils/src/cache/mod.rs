@@ -0,0 +1,3 @@
+pub mod cached_build;
+pub mod clean;
+pub mod input_hash;
@@ -5,6 +5,16 @@ use clap::Parser;
 #[command(author, version, about, long_about=None)]
 #[command(propagate_version = true)]
 pub struct Cli {
+    #[arg(long, global = true)]
+    /// directory of the coordinator socket, pid and log files, defaults to
+    /// `NODE_SPACE_RUNTIME_DIR`, `$XDG_RUNTIME_DIR/node-space` or `/tmp/node-space-<uid>`
+    pub runtime_dir: Option<String>,
+
+    #[arg(long, global = true)]
+    /// coordinator instance to use, defaults to `NODE_SPACE_INSTANCE` or a name derived from the
+    /// git root of the current directory
+    pub instance: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -1,5 +1,6 @@
 use std::{
     io::Error,
+    os::unix::process::CommandExt,
     process::{Child, Command, Stdio},
 };
 
@@ -23,6 +24,26 @@ pub fn handle_command_result(spwn: Result<Child, Error>) -> Result<bool, BuildEr
     Ok(true)
 }
 
+/// spawn `npm run <command_name>` in its own process group without waiting for it, so the whole
+/// build (npm and the tools it starts) can be stopped together
+/// stdout and stderr are piped and have to be read by the caller
+pub fn spawn_node_command(path: &str, command_name: &str) -> Result<Child, BuildError> {
+    let spwn = Command::new("npm")
+        .arg("run")
+        .arg(command_name)
+        .current_dir(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn();
+
+    match spwn {
+        Ok(value) => Ok(value),
+        Err(error) => Err(BuildError::CantSpwnBuilCommand(error.to_string())),
+    }
+}
+
 pub fn run_node_command(path: &str, command_name: &str) -> Result<bool, BuildError> {
     let spwn = Command::new("npm")
         .arg("run")
@@ -34,3 +55,18 @@ pub fn run_node_command(path: &str, command_name: &str) -> Result<bool, BuildErr
 
     handle_command_result(spwn)
 }
+
+/// installs a single package in the project without touching package.json,
+/// `package_spec` can be a bare name or `name@version`
+pub fn run_npm_install(path: &str, package_spec: &str) -> Result<bool, BuildError> {
+    let spwn = Command::new("npm")
+        .arg("install")
+        .arg("--no-save")
+        .arg(package_spec)
+        .current_dir(path)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn();
+
+    handle_command_result(spwn)
+}
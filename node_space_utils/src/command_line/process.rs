@@ -1,14 +1,69 @@
-use std::process::Command;
+use std::fs;
 
 use crate::errors::process::ProcessError;
 
+/// a zombie already exited and only waits for its parent to reap it
+fn is_zombie(pid: u32) -> bool {
+    let stat = match fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+
+    // the command name can contain spaces, the state is the first field after it
+    match stat.rfind(')') {
+        Some(index) => stat[index + 1..].trim_start().starts_with('Z'),
+        None => false,
+    }
+}
+
+/// signal 0 only checks that the process exists, `EPERM` means it belongs to another user
 pub fn is_process_running(pid: u32) -> Result<bool, ProcessError> {
-    let status = Command::new("kill").arg("-0").arg(pid.to_string()).status();
+    if pid == 0 || pid > i32::MAX as u32 {
+        return Ok(false);
+    }
+
+    let result = unsafe { libc::kill(pid as i32, 0) };
+
+    if result != 0 {
+        let error = std::io::Error::last_os_error();
+
+        return match error.raw_os_error() {
+            Some(libc::ESRCH) => Ok(false),
+            Some(libc::EPERM) => Ok(true),
+            _ => Err(ProcessError::ErrorCheckingProcessIsRunning(
+                error.to_string(),
+            )),
+        };
+    }
 
-    match status {
-        Ok(value) => Ok(value.success()),
-        Err(error) => Err(ProcessError::ErrorCheckingProcessIsRunning(
-            error.to_string(),
-        )),
+    Ok(!is_zombie(pid))
+}
+
+pub fn terminate_process(pid: u32) -> Result<(), ProcessError> {
+    let result = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
+
+    if result != 0 {
+        return Err(ProcessError::Other(format!(
+            "can't terminate process {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        )));
     }
+
+    Ok(())
+}
+
+/// send SIGTERM to every process in the group led by `pid`
+pub fn terminate_process_group(pid: u32) -> Result<(), ProcessError> {
+    let result = unsafe { libc::kill(-(pid as i32), libc::SIGTERM) };
+
+    if result != 0 {
+        return Err(ProcessError::Other(format!(
+            "can't terminate process group {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        )));
+    }
+
+    Ok(())
 }
@@ -2,7 +2,9 @@ use clap::Subcommand;
 
 use crate::args::build_args::BuildArgs;
 use crate::args::link_args::LinkArgs;
+use crate::args::unlink_args::UnlinkArgs;
 
+use super::cache::CacheBridge;
 use super::coordinator::CoordinatorBridge;
 use super::dependencies::DependenciesBridge;
 use super::group::GroupCommandBridge;
@@ -12,10 +14,12 @@ use super::server::ServerBridge;
 #[derive(Subcommand)]
 pub enum Commands {
     Link(LinkArgs),
+    Unlink(UnlinkArgs),
     Project(ProjectCommandBridge),
     Group(GroupCommandBridge),
     Deps(DependenciesBridge),
     Build(BuildArgs),
     Coordinator(CoordinatorBridge),
     Server(ServerBridge),
+    Cache(CacheBridge),
 }
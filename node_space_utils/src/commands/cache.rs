@@ -0,0 +1,14 @@
+use clap::{Parser, Subcommand};
+
+use crate::args::cache_args::CacheCleanArgs;
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    Clean(CacheCleanArgs),
+}
+
+#[derive(Parser)]
+pub struct CacheBridge {
+    #[structopt(subcommand)]
+    pub cache_commands: CacheCommands,
+}
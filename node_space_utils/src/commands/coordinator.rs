@@ -1,11 +1,19 @@
 use clap::{Parser, Subcommand};
 
-use crate::args::coordinator_args::{CoordinatorLogArgs, CoordinatorStartArgs};
+use crate::args::coordinator_args::{
+    CoordinatorListArgs, CoordinatorLogArgs, CoordinatorRestartArgs, CoordinatorStartArgs,
+    CoordinatorStatusArgs, CoordinatorStopArgs, CoordinatorUnwatchArgs,
+};
 
 #[derive(Subcommand)]
 pub enum CoordinatorCommands {
     Start(CoordinatorStartArgs),
     Log(CoordinatorLogArgs),
+    Status(CoordinatorStatusArgs),
+    Stop(CoordinatorStopArgs),
+    Restart(CoordinatorRestartArgs),
+    Unwatch(CoordinatorUnwatchArgs),
+    List(CoordinatorListArgs),
 }
 
 #[derive(Parser)]
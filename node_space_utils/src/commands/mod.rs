@@ -1,4 +1,5 @@
 pub mod base_command;
+pub mod cache;
 pub mod coordinator;
 pub mod dependencies;
 pub mod group;
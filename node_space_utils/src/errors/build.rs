@@ -6,6 +6,7 @@ pub enum BuildError {
     CantSpwnBuilCommand(String),
     CantWaitForChildProcess(String),
     ChildCommandFailed(String),
+    DependencyCycle(String),
     Other(String),
 }
 
@@ -21,6 +22,9 @@ impl fmt::Display for BuildError {
             BuildError::ChildCommandFailed(ref message) => {
                 write!(f, "Child command failed: {}", message)
             }
+            BuildError::DependencyCycle(ref message) => {
+                write!(f, "Dependency cycle detected: {}", message)
+            }
             BuildError::Other(ref message) => {
                 write!(f, "Error building project: {}", message)
             }
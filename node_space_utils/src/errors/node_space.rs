@@ -21,14 +21,26 @@ pub enum NodeSpaceError {
     CantOpenPIDFile(String),
     CantParsePIDNumber(String),
     CantStartCoordinator(String),
+    CantStopCoordinator(String),
     CantWriteToPIDFile(String),
     CantPlaceSigTermHandler(String),
     CantCreateLogFile(String),
+    CantCreateRuntimeDir(String),
+    InvalidInstanceName(String),
+    CoordinatorAlreadyRunning(String),
+    CantSaveCoordinatorState(String),
+    CantLoadCoordinatorState(String),
     CantWriteLogFile(String),
+    CantReadLogFile(String),
+    InvalidLogFilter(String),
+    InvalidLogSettings(String),
+    InvalidWatchSettings(String),
+    CantSaveBuildCache(String),
     CantCreateSocketFile(String),
     MissingProject,
     InvalidRoutesConfig(String),
     ServerError(String),
+    InvalidTlsConfig(String),
 }
 
 impl fmt::Display for NodeSpaceError {
@@ -85,6 +97,10 @@ impl fmt::Display for NodeSpaceError {
                 write!(f, "Can't start coordinator: {}", message)
             }
 
+            NodeSpaceError::CantStopCoordinator(ref message) => {
+                write!(f, "Can't stop coordinator: {}", message)
+            }
+
             NodeSpaceError::CantWriteToPIDFile(ref message) => {
                 write!(f, "Can't write to pid file: {}", message)
             }
@@ -95,9 +111,43 @@ impl fmt::Display for NodeSpaceError {
             NodeSpaceError::CantCreateLogFile(ref message) => {
                 write!(f, "Can't create log file: {}", message)
             }
+            NodeSpaceError::CantCreateRuntimeDir(ref message) => {
+                write!(f, "Can't create runtime dir: {}", message)
+            }
+            NodeSpaceError::InvalidInstanceName(ref message) => {
+                write!(
+                    f,
+                    "Invalid instance name, only letters, digits, '-', '_' and '.' are allowed: {}",
+                    message
+                )
+            }
+            NodeSpaceError::CoordinatorAlreadyRunning(ref message) => {
+                write!(f, "Coordinator already running: {}", message)
+            }
+            NodeSpaceError::CantSaveCoordinatorState(ref message) => {
+                write!(f, "Can't save coordinator state: {}", message)
+            }
+            NodeSpaceError::CantLoadCoordinatorState(ref message) => {
+                write!(f, "Can't load coordinator state: {}", message)
+            }
             NodeSpaceError::CantWriteLogFile(ref message) => {
                 write!(f, "Can't write to log file: {}", message)
             }
+            NodeSpaceError::CantReadLogFile(ref message) => {
+                write!(f, "Can't read log file: {}", message)
+            }
+            NodeSpaceError::InvalidLogFilter(ref message) => {
+                write!(f, "Invalid log filter: {}", message)
+            }
+            NodeSpaceError::InvalidLogSettings(ref message) => {
+                write!(f, "Invalid log settings: {}", message)
+            }
+            NodeSpaceError::InvalidWatchSettings(ref message) => {
+                write!(f, "Invalid watch settings: {}", message)
+            }
+            NodeSpaceError::CantSaveBuildCache(ref message) => {
+                write!(f, "Can't save build cache: {}", message)
+            }
             NodeSpaceError::CantCreateSocketFile(ref message) => {
                 write!(f, "Can't create socket file: {}", message)
             }
@@ -112,6 +162,10 @@ impl fmt::Display for NodeSpaceError {
             NodeSpaceError::ServerError(ref message) => {
                 write!(f, "Error serving file: {}", message)
             }
+
+            NodeSpaceError::InvalidTlsConfig(ref message) => {
+                write!(f, "Invalid server tls config: {}", message)
+            }
         }
     }
 }
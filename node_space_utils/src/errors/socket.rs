@@ -5,6 +5,8 @@ use std::fmt;
 pub enum SocketError {
     ErrorConnectingToSocket(String),
     ErrorSendingDataInSocket(String),
+    InvalidResponse(String),
+    RequestRejected(String),
     Other(String),
 }
 
@@ -18,6 +20,21 @@ impl fmt::Display for SocketError {
                 write!(f, "Socket error, can't send data to socket: {}", message)
             }
 
+            SocketError::InvalidResponse(ref message) => {
+                write!(
+                    f,
+                    "Socket error, invalid response from coordinator: {}",
+                    message
+                )
+            }
+            SocketError::RequestRejected(ref message) => {
+                write!(
+                    f,
+                    "Socket error, coordinator rejected the request: {}",
+                    message
+                )
+            }
+
             SocketError::Other(ref message) => {
                 write!(f, "Socket error: {}", message)
             }
@@ -6,6 +6,10 @@ pub enum SymlinkError {
     InvalidSymlink,
     MissingLinkToTargetName,
     CantRemoveExistingDir(String),
+    CantRemoveSymlink(String),
+    MissingSymlink(String),
+    CantBackupPackage(String),
+    CantRestoreBackup(String),
     Other(String),
 }
 
@@ -22,6 +26,26 @@ impl fmt::Display for SymlinkError {
             SymlinkError::CantRemoveExistingDir(ref message) => {
                 write!(f, "Symlink error, can't remove existing dir: {}", message)
             }
+            SymlinkError::CantRemoveSymlink(ref message) => {
+                write!(f, "Symlink error, can't remove symlink: {}", message)
+            }
+            SymlinkError::MissingSymlink(ref message) => {
+                write!(f, "Symlink error, no such linked package: {}", message)
+            }
+            SymlinkError::CantBackupPackage(ref message) => {
+                write!(
+                    f,
+                    "Symlink error, can't backup installed package: {}",
+                    message
+                )
+            }
+            SymlinkError::CantRestoreBackup(ref message) => {
+                write!(
+                    f,
+                    "Symlink error, can't restore package backup: {}",
+                    message
+                )
+            }
             SymlinkError::Other(ref message) => {
                 write!(f, "Symlink error: {}", message)
             }
@@ -1,6 +1,8 @@
 use crate::build_command::build_project::handle_build_command;
+use crate::cache::clean::handle_cache_clean;
 use crate::cli_manager::Cli;
 use crate::commands::base_command::Commands;
+use crate::commands::cache::CacheCommands;
 use crate::commands::coordinator::CoordinatorCommands;
 use crate::commands::dependencies::DependenciesCommands;
 use crate::commands::group::GroupCommands;
@@ -16,16 +18,32 @@ use crate::projects::add::add_project;
 use crate::projects::show::show_all_projects;
 use crate::server::config::handle_server_config;
 use crate::server::start::handle_server_start;
+use crate::unlink_package::handle_unlink_command;
+use crate::watch_coordinator::client::handle_coordinator_status::handle_coordinator_status;
+use crate::watch_coordinator::client::list_coordinators::handle_coordinator_list;
+use crate::watch_coordinator::client::restart_coordinator::handle_coordinator_restart;
+use crate::watch_coordinator::client::stop_coordinator::handle_coordinator_stop;
+use crate::watch_coordinator::client::unwatch_project::handle_coordinator_unwatch;
 use crate::watch_coordinator::coordinator::handle_coordiantor_logs::handle_coordinator_logs;
 use crate::watch_coordinator::coordinator::handle_start_coordinator::handle_start_coordinator;
+use crate::watch_coordinator::runtime_dir::{select_instance, RUNTIME_DIR_ENV};
 
 use clap::Parser;
+use std::env;
 
 pub async fn handle_cli() -> Result<bool, NodeSpaceError> {
     let cli = Cli::parse();
 
+    // the coordinator is spawned as a child process, the env var carries the dir over to it
+    if let Some(runtime_dir) = &cli.runtime_dir {
+        env::set_var(RUNTIME_DIR_ENV, runtime_dir);
+    }
+
+    select_instance(cli.instance.as_deref())?;
+
     match &cli.command {
         Commands::Link(link_args) => handle_link_command(link_args),
+        Commands::Unlink(unlink_args) => handle_unlink_command(unlink_args),
         Commands::Project(project_command) => match &project_command.project_commands {
             ProjectCommands::Add(project_args) => add_project(&project_args),
             ProjectCommands::Show => show_all_projects(),
@@ -45,6 +63,11 @@ pub async fn handle_cli() -> Result<bool, NodeSpaceError> {
         Commands::Coordinator(coordinator_args) => match &coordinator_args.coordinator_commands {
             CoordinatorCommands::Start(start_args) => handle_start_coordinator(start_args),
             CoordinatorCommands::Log(log_args) => handle_coordinator_logs(log_args),
+            CoordinatorCommands::Status(status_args) => handle_coordinator_status(status_args),
+            CoordinatorCommands::Stop(stop_args) => handle_coordinator_stop(stop_args),
+            CoordinatorCommands::Restart(restart_args) => handle_coordinator_restart(restart_args),
+            CoordinatorCommands::Unwatch(unwatch_args) => handle_coordinator_unwatch(unwatch_args),
+            CoordinatorCommands::List(list_args) => handle_coordinator_list(list_args),
         },
         Commands::Server(server_args) => match &server_args.server_commands {
             ServerCommands::Start(server_start_args) => {
@@ -54,5 +77,8 @@ pub async fn handle_cli() -> Result<bool, NodeSpaceError> {
                 handle_server_config(server_config_args).await
             }
         },
+        Commands::Cache(cache_args) => match &cache_args.cache_commands {
+            CacheCommands::Clean(clean_args) => handle_cache_clean(clean_args),
+        },
     }
 }
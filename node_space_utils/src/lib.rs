@@ -1,5 +1,6 @@
 pub mod args;
 pub mod build_command;
+pub mod cache;
 pub mod cli_manager;
 pub mod command_line;
 pub mod commands;
@@ -15,7 +16,9 @@ pub mod path_utils;
 pub mod projects;
 pub mod retry;
 pub mod server;
-pub mod socket;
 pub mod symlink_utils;
+pub mod time_utils;
+pub mod unlink_package;
 pub mod watch_coordinator;
+pub mod watch_filter;
 pub mod watcher_utils;
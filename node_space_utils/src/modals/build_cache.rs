@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::node_space::NodeSpaceError, path_utils::expand_tilde, time_utils::get_unix_timestamp,
+};
+
+use super::package::Package;
+
+const BUILD_CACHE_PATH_STR: &str = "~/.cache/node-space/build-cache.json";
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct BuildCacheEntry {
+    pub input_hash: String,
+    /// unix timestamp in seconds
+    pub built_at: u64,
+}
+
+/// input hash of the last successful build of every project, keyed by project path
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct BuildCache {
+    pub entries: HashMap<String, BuildCacheEntry>,
+    #[serde(skip)]
+    cache_path: PathBuf,
+}
+
+impl BuildCache {
+    /// a missing or broken cache file is an empty cache
+    pub fn new() -> Result<Self, NodeSpaceError> {
+        let cache_path = expand_tilde(BUILD_CACHE_PATH_STR)?;
+
+        let json_data = fs::read_to_string(&cache_path).unwrap_or_else(|_| String::from("{}"));
+
+        let mut cache: BuildCache = serde_json::from_str(&json_data).unwrap_or_default();
+
+        cache.cache_path = cache_path;
+
+        Ok(cache)
+    }
+
+    pub fn get_cache_path(&self) -> &PathBuf {
+        &self.cache_path
+    }
+
+    /// the coordinator and local watchers share the file, it is written to a temporary file of
+    /// the process and renamed so readers never see a partial cache
+    pub fn save(&self) -> Result<(), NodeSpaceError> {
+        let json_data = match serde_json::to_string_pretty(self) {
+            Ok(value) => value,
+            Err(error) => return Err(NodeSpaceError::CantSaveBuildCache(error.to_string())),
+        };
+
+        if let Some(parent_dir) = self.cache_path.parent() {
+            if let Err(error) = fs::create_dir_all(parent_dir) {
+                return Err(NodeSpaceError::CantSaveBuildCache(error.to_string()));
+            }
+        }
+
+        let mut temp_path = self.cache_path.clone().into_os_string();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+
+        if let Err(error) = fs::write(&temp_path, json_data) {
+            return Err(NodeSpaceError::CantSaveBuildCache(error.to_string()));
+        }
+
+        match fs::rename(&temp_path, &self.cache_path) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(NodeSpaceError::CantSaveBuildCache(error.to_string())),
+        }
+    }
+
+    /// the inputs match the last successful build and its output is still there, a missing or
+    /// empty output dir means the output was cleaned and the project is built again
+    pub fn is_up_to_date(&self, project: &Package, input_hash: &str) -> bool {
+        let is_same_input = match self.entries.get(&project.path) {
+            Some(entry) => entry.input_hash == input_hash,
+            None => false,
+        };
+
+        let output_dir = Path::new(&project.path).join(&project.output_name);
+
+        let has_output = match fs::read_dir(output_dir) {
+            Ok(mut entries) => entries.next().is_some(),
+            Err(_) => false,
+        };
+
+        is_same_input && has_output
+    }
+
+    /// reload before writing, builds of other processes may have been recorded in the meantime
+    pub fn record(path: &str, input_hash: &str) -> Result<(), NodeSpaceError> {
+        let mut cache = Self::new()?;
+
+        cache.entries.insert(
+            path.to_string(),
+            BuildCacheEntry {
+                input_hash: input_hash.to_string(),
+                built_at: get_unix_timestamp(),
+            },
+        );
+
+        cache.save()
+    }
+
+    /// returns the number of removed entries
+    pub fn remove(paths: &[String]) -> Result<usize, NodeSpaceError> {
+        let mut cache = Self::new()?;
+        let count = cache.entries.len();
+
+        cache.entries.retain(|path, _| !paths.contains(path));
+
+        let removed_count = count - cache.entries.len();
+
+        if removed_count > 0 {
+            cache.save()?;
+        }
+
+        Ok(removed_count)
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// outcome of the last coordinator build of a project
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct BuildResult {
+    pub success: bool,
+    /// unix timestamp in seconds
+    pub finished_at: u64,
+    pub duration_ms: u64,
+}
+
+impl BuildResult {
+    pub fn new(success: bool, finished_at: u64, duration_ms: u64) -> Self {
+        BuildResult {
+            success,
+            finished_at,
+            duration_ms,
+        }
+    }
+}
@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use crate::errors::node_space::NodeSpaceError;
 use crate::errors::symlink::SymlinkError;
 use crate::package_utils::{find_package_by_name, is_package_exist};
-use crate::path_utils::get_package_path_from_node_modules;
+use crate::path_utils::{
+    backup_package_from_node_modules, get_package_path_from_node_modules,
+    restore_package_to_node_modules,
+};
 use crate::symlink_utils::handle_link_candidate;
 use crate::{
     errors::{config_file::ConfigFileError, invalid_project::InvalidNodeProjectError},
@@ -15,9 +18,11 @@ use crate::{
 
 use super::link_action::LinkAction;
 use super::package::Package;
+use super::package_backup::PackageBackup;
 use super::server_config::ServerConfig;
 
 const CONFIG_PATH_STR: &str = "~/.config/node-space/space-data.json";
+const BACKUPS_PATH_STR: &str = "~/.config/node-space/backups";
 
 #[derive(Serialize, Deserialize, Default, Debug)]
 pub struct ConfigFile {
@@ -26,6 +31,8 @@ pub struct ConfigFile {
     pub symlinks: HashMap<String, Vec<Package>>,
     pub groups: HashMap<String, Vec<Package>>,
     pub server_config: HashMap<String, ServerConfig>,
+    #[serde(default)]
+    pub backups: Vec<PackageBackup>,
     config_path: PathBuf,
 }
 
@@ -87,23 +94,42 @@ impl ConfigFile {
 
         let package = find_package_by_name(&self.linked_packages, link_to_name)?;
 
-        let list = self.symlinks.get_mut(&current_package.name).unwrap();
-
-        list.push(package.clone());
+        let backup_path = self.get_backup_path(&current_package.name, &package.name)?;
 
         let symlink_path =
             get_package_path_from_node_modules(&current_package.path, &package.name)?;
 
+        let was_backed_up =
+            backup_package_from_node_modules(&current_package.path, &package.name, &backup_path)?;
+
         dbg!(&symlink_path, &package.path);
 
-        match symlink(&package.path, symlink_path) {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(NodeSpaceError::ConfigFileError(
-                    ConfigFileError::FailedToCreateSymLink(error.to_string()),
-                ))
+        if let Err(error) = symlink(&package.path, symlink_path) {
+            // the backup record is never saved, put the installed package back where it was
+            if was_backed_up {
+                restore_package_to_node_modules(
+                    &current_package.path,
+                    &package.name,
+                    &backup_path,
+                )?;
             }
-        };
+
+            return Err(NodeSpaceError::ConfigFileError(
+                ConfigFileError::FailedToCreateSymLink(error.to_string()),
+            ));
+        }
+
+        let list = self.symlinks.get_mut(&current_package.name).unwrap();
+
+        list.push(package.clone());
+
+        if was_backed_up {
+            self.add_backup(PackageBackup::new(
+                current_package.path.clone(),
+                package.name.clone(),
+                backup_path.to_string_lossy().to_string(),
+            ));
+        }
 
         let result = self.save()?;
 
@@ -122,9 +148,36 @@ impl ConfigFile {
         Ok(result)
     }
 
+    /// adding a known project again replaces its watch settings when new ones are given
     pub fn add_project(&mut self, current_package: &Package) -> Result<(), NodeSpaceError> {
-        if !is_package_exist(&self.projects, &current_package.path) {
-            self.projects.push(current_package.clone())
+        let existing_project = self
+            .projects
+            .iter_mut()
+            .find(|project| project.path == current_package.path);
+
+        match existing_project {
+            Some(project) => {
+                if !current_package.include.is_empty() {
+                    project.include = current_package.include.clone();
+                }
+
+                if !current_package.exclude.is_empty() {
+                    project.exclude = current_package.exclude.clone();
+                }
+
+                if current_package.debounce_ms.is_some() {
+                    project.debounce_ms = current_package.debounce_ms;
+                }
+
+                if current_package.build_on_start.is_some() {
+                    project.build_on_start = current_package.build_on_start;
+                }
+
+                if !current_package.trigger_events.is_empty() {
+                    project.trigger_events = current_package.trigger_events.clone();
+                }
+            }
+            None => self.projects.push(current_package.clone()),
         }
 
         let result = self.save()?;
@@ -184,6 +237,116 @@ impl ConfigFile {
         }
     }
 
+    /// copy the watch settings saved for the project at the same path, packages stored in links
+    /// and symlinks are snapshots and don't follow `project add`
+    pub fn with_watch_settings(&self, package: &Package) -> Package {
+        let mut result = package.clone();
+
+        if let Some(project) = self
+            .projects
+            .iter()
+            .find(|project| project.path == package.path)
+        {
+            result.include = project.include.clone();
+            result.exclude = project.exclude.clone();
+            result.debounce_ms = project.debounce_ms;
+            result.build_on_start = project.build_on_start;
+            result.trigger_events = project.trigger_events.clone();
+        }
+
+        result
+    }
+
+    /// get the packages linked into a project, filtered by the linked package name or alias
+    pub fn find_project_symlinks(
+        &self,
+        project_name: &str,
+        link_name: Option<&str>,
+    ) -> Vec<Package> {
+        let symlinks = match self.symlinks.get(project_name) {
+            Some(value) => value,
+            None => return Vec::new(),
+        };
+
+        symlinks
+            .iter()
+            .filter(|package| match link_name {
+                Some(name) => package.is_named(name),
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn remove_symlink(
+        &mut self,
+        project_name: &str,
+        package: &Package,
+    ) -> Result<(), NodeSpaceError> {
+        if let Some(list) = self.symlinks.get_mut(project_name) {
+            list.retain(|current| current.path != package.path);
+
+            if list.is_empty() {
+                self.symlinks.remove(project_name);
+            }
+        }
+
+        self.save()?;
+
+        Ok(())
+    }
+
+    pub fn remove_linked_package(&mut self, path: &str) -> Result<(), NodeSpaceError> {
+        self.linked_packages.retain(|current| current.path != path);
+
+        self.save()?;
+
+        Ok(())
+    }
+
+    /// backups are stored per project: `~/.config/node-space/backups/<project>/<package>`
+    pub fn get_backup_path(
+        &self,
+        project_name: &str,
+        package_name: &str,
+    ) -> Result<PathBuf, NodeSpaceError> {
+        let backups_path = expand_tilde(BACKUPS_PATH_STR)?;
+
+        Ok(backups_path.join(project_name).join(package_name))
+    }
+
+    fn add_backup(&mut self, backup: PackageBackup) {
+        self.backups.retain(|current| {
+            current.project_path != backup.project_path
+                || current.package_name != backup.package_name
+        });
+
+        self.backups.push(backup);
+    }
+
+    pub fn find_backup(&self, project_path: &str, package_name: &str) -> Option<PackageBackup> {
+        self.backups
+            .iter()
+            .find(|backup| {
+                backup.project_path == project_path && backup.package_name == package_name
+            })
+            .cloned()
+    }
+
+    pub fn remove_backup(
+        &mut self,
+        project_path: &str,
+        package_name: &str,
+    ) -> Result<(), NodeSpaceError> {
+        self.backups.retain(|backup| {
+            backup.project_path != project_path || backup.package_name != package_name
+        });
+
+        self.save()?;
+
+        Ok(())
+    }
+
     pub fn find_package(&self, path: String) -> Result<Package, NodeSpaceError> {
         let projects = self.projects.clone();
 
@@ -0,0 +1,108 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// content of the changed files as of the last successful build, saving a file without edits or
+/// switching back and forth between branches gives the same hashes and doesn't need a new build
+#[derive(Debug, Default)]
+pub struct ContentHashes {
+    /// `None` for files that were removed or can't be read
+    hashes: HashMap<PathBuf, Option<u64>>,
+    /// hashes of the build that is waiting for its result
+    pending: HashMap<PathBuf, Option<u64>>,
+}
+
+impl ContentHashes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn hash_file(path: &Path) -> Option<u64> {
+        let content = fs::read(path).ok()?;
+        let mut hasher = DefaultHasher::new();
+
+        content.hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+
+    /// true when one of `paths` differs from the last successful build, the current hashes are
+    /// kept until the build reports its result. files changed for the first time since the
+    /// watcher started always count as changed
+    pub fn update(&mut self, paths: &[PathBuf]) -> bool {
+        let mut is_changed = false;
+
+        for path in paths {
+            let hash = Self::hash_file(path);
+
+            match self.hashes.get(path) {
+                Some(previous_hash) if *previous_hash == hash => {}
+                _ => is_changed = true,
+            }
+
+            self.pending.insert(path.clone(), hash);
+        }
+
+        is_changed
+    }
+
+    /// store the hashes of the build once it succeeded
+    pub fn commit(&mut self) {
+        self.hashes.extend(self.pending.drain());
+    }
+
+    /// forget the files of a failed or cancelled build, their next change builds again
+    pub fn discard(&mut self) {
+        for (path, _) in self.pending.drain() {
+            self.hashes.remove(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_temp_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "node-space-content-hashes-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn only_a_successful_build_keeps_the_hashes() {
+        let path = create_temp_file("settle", "a");
+        let paths = vec![path.clone()];
+        let mut content_hashes = ContentHashes::new();
+
+        assert!(content_hashes.update(&paths));
+
+        content_hashes.discard();
+
+        assert!(content_hashes.update(&paths));
+
+        content_hashes.commit();
+
+        assert!(!content_hashes.update(&paths));
+
+        fs::write(&path, "b").unwrap();
+
+        assert!(content_hashes.update(&paths));
+
+        content_hashes.discard();
+        fs::write(&path, "a").unwrap();
+
+        assert!(content_hashes.update(&paths));
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,50 +1,141 @@
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use notify::RecommendedWatcher;
 use notify_debouncer_full::{Debouncer, RecommendedCache};
 
 use crate::watch_coordinator::coordinator::handle_signals::handle_termination_signals;
-use crate::watch_coordinator::coordinator::listener_utils::init_listener;
-use crate::watch_coordinator::coordinator::log_utils::{create_logging_file, log_to_file};
+use crate::watch_coordinator::coordinator::listener_utils::{init_listener, wake_listener};
+use crate::watch_coordinator::coordinator::lock_file::CoordinatorLock;
+use crate::watch_coordinator::coordinator::log_utils::{
+    create_logging_file, CoordinatorLogger, LogSettings,
+};
+use crate::watch_coordinator::coordinator::process_stream_request::release_attached_projects;
 use crate::watch_coordinator::coordinator::socket_file::create_socket_file;
 use crate::watch_coordinator::coordinator::thread_utils::send_thread_kill_signal;
 use crate::{
     errors::{node_space::NodeSpaceError, socket::SocketError},
+    time_utils::get_unix_timestamp,
     watcher_utils::create_watcher_instance,
 };
 
+use super::build_result::BuildResult;
+use super::content_hashes::ContentHashes;
+
+use super::coordinator_build_scheduler::{CoordinatorBuildScheduler, SchedulerMessage};
+use super::coordinator_control::ControlMessage;
 use super::coordinator_listener::CoordinatorListener;
 use super::coordinator_pid_manager::CoordinatorPIDManager;
+use super::coordinator_state::CoordinatorState;
+use super::coordinator_subscribers::CoordinatorSubscribers;
 use super::coordinator_updates_manager::CoordinatorUpdatesManager;
 use super::coordinator_watcher_handler::CoordinatorWatcherHandler;
 use super::package::Package;
 
-pub type SharedWatcherClone = Arc<Mutex<Debouncer<RecommendedWatcher, RecommendedCache>>>;
+pub type CoordinatorWatcher = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+/// only merges the events of a single save, the debounce interval of every project is applied by
+/// the updates manager
+const WATCHER_EVENT_TIMEOUT: Duration = Duration::from_millis(100);
 
 pub struct Coordinator {
+    /// every project that sent a register request, used to restore them on restart
+    pub registered_projects: Vec<Package>,
+    /// who asked for every registered project: the project itself for a registration of its own
+    /// client, the parent project for registrations made on its behalf
+    pub registration_owners: HashMap<String, Vec<String>>,
+    /// number of open `build --watch` streams per project, only the paths are saved since the
+    /// streams die with the coordinator
+    pub attached_projects: HashMap<String, usize>,
     pub watchers_target: Vec<Package>,
     pub active_watchers: Vec<String>,
     pub dependencies_to_projects_map: HashMap<String, Vec<String>>,
     pub projects_to_dependencies_map: HashMap<String, Vec<String>>,
+    pub last_builds: HashMap<String, BuildResult>,
+    /// content of the changed files of every project, settled by the scheduler with the build
+    /// result
+    pub content_hashes: HashMap<String, ContentHashes>,
+    pub max_jobs: usize,
+    pub log_settings: LogSettings,
+    /// skip loading the saved state on start
+    pub fresh: bool,
+    /// unix timestamp in seconds
+    pub started_at: u64,
 }
 
 impl Coordinator {
-    pub fn new() -> Self {
+    pub fn new(max_jobs: usize, log_settings: LogSettings, fresh: bool) -> Self {
         Self {
+            registered_projects: Vec::new(),
+            registration_owners: HashMap::new(),
+            attached_projects: HashMap::new(),
             watchers_target: Vec::new(),
             active_watchers: Vec::new(),
             dependencies_to_projects_map: HashMap::new(),
             projects_to_dependencies_map: HashMap::new(),
+            last_builds: HashMap::new(),
+            content_hashes: HashMap::new(),
+            max_jobs,
+            log_settings,
+            fresh,
+            started_at: get_unix_timestamp(),
+        }
+    }
+
+    /// load the registrations saved by the previous coordinator, returns true when any were loaded
+    fn restore_state(&mut self, shared_logger: &CoordinatorLogger) -> bool {
+        if self.fresh {
+            shared_logger.info("starting fresh, removing saved state");
+
+            if let Err(error) = CoordinatorState::delete() {
+                shared_logger.warn(&error.to_string());
+            }
+
+            return false;
+        }
+
+        let state = match CoordinatorState::load() {
+            Ok(Some(value)) => value,
+            Ok(None) => return false,
+            Err(error) => {
+                shared_logger.warn(&format!("ignoring saved state: {}", error));
+
+                return false;
+            }
+        };
+
+        shared_logger.info(&format!(
+            "restored {} registered projects from saved state",
+            state.registered_projects.len()
+        ));
+
+        let attached_projects = state.attached_projects.clone();
+
+        state.apply(self);
+
+        if !attached_projects.is_empty() {
+            release_attached_projects(self, &attached_projects, shared_logger);
+
+            if let Err(error) = CoordinatorState::from_coordinator(self).save() {
+                shared_logger.warn(&error.to_string());
+            }
         }
+
+        !self.watchers_target.is_empty()
     }
 
-    pub fn start(self) -> Result<bool, NodeSpaceError> {
-        let shared_logger = create_logging_file()?;
+    pub fn start(mut self) -> Result<bool, NodeSpaceError> {
+        // taken before touching the log, socket and pid files of a coordinator that may be running
+        let _lock = CoordinatorLock::acquire()?;
+
+        let shared_logger = create_logging_file(self.log_settings)?;
+
+        shared_logger.debug("created logger");
 
-        log_to_file("created logger", &shared_logger)?;
+        let is_state_restored = self.restore_state(&shared_logger);
 
         let pid = std::process::id();
         let pid_manager = CoordinatorPIDManager::new();
@@ -52,57 +143,67 @@ impl Coordinator {
         pid_manager.write_pid(pid)?;
 
         let message = format!("Coordinator started with PID: {}", pid.to_string());
-        log_to_file(&message, &shared_logger)?;
+        shared_logger.info(&message);
 
         create_socket_file(&shared_logger)?;
 
-        log_to_file("created socket file", &shared_logger)?;
+        shared_logger.debug("created socket file");
         let listener = init_listener(&shared_logger)?;
 
-        let term = handle_termination_signals()?;
+        let (control_sender, control_reciever) = std::sync::mpsc::channel();
+
+        handle_termination_signals(control_sender.clone())?;
+
+        if is_state_restored {
+            let _ = control_sender.send(ControlMessage::SyncWatchers);
+        }
 
         let (kill_thread_sender_stream, kill_thread_reciever_stream) = std::sync::mpsc::channel();
-        let (kill_thread_sender_file_handler, kill_thread_reciever_file_handler) =
-            std::sync::mpsc::channel();
 
+        let max_jobs = self.max_jobs;
         let lock = Arc::new(Mutex::new(self));
 
-        let (reciver, current_watcher) = create_watcher_instance()?;
+        let subscribers = CoordinatorSubscribers::new();
+
+        let (scheduler_sender, thread_handle_scheduler) =
+            CoordinatorBuildScheduler::new(max_jobs, subscribers.clone())
+                .start(Arc::clone(&lock), shared_logger.clone());
 
-        log_to_file("created watcher", &shared_logger)?;
+        let (reciver, current_watcher) = create_watcher_instance(WATCHER_EVENT_TIMEOUT)?;
 
-        let shared_watcher = Arc::new(Mutex::new(current_watcher));
+        shared_logger.debug("created watcher");
 
         let thread_handle_listener = CoordinatorListener::handle_listener(
             listener,
             Arc::clone(&lock),
+            scheduler_sender.clone(),
+            control_sender,
+            subscribers,
             kill_thread_reciever_stream,
-            Arc::clone(&shared_logger),
+            shared_logger.clone(),
         );
 
         let thread_handle_file_change = CoordinatorUpdatesManager::handle_file_change(
             reciver,
             Arc::clone(&lock),
-            kill_thread_reciever_file_handler,
-            Arc::clone(&shared_logger),
+            scheduler_sender.clone(),
+            shared_logger.clone(),
         );
 
         CoordinatorWatcherHandler::handle_watcher(
             Arc::clone(&lock),
-            Arc::clone(&shared_watcher),
-            term,
-            Arc::clone(&shared_logger),
+            current_watcher,
+            control_reciever,
+            shared_logger.clone(),
         );
 
-        log_to_file("send kill kill_thread_sender_stream", &shared_logger)?;
+        shared_logger.debug("send kill kill_thread_sender_stream");
         send_thread_kill_signal(kill_thread_sender_stream);
-
-        log_to_file("send kill kill_thread_sender_file_handler", &shared_logger)?;
-        send_thread_kill_signal(kill_thread_sender_file_handler);
+        wake_listener(&shared_logger);
 
         match thread_handle_listener.join() {
             Ok(_) => {
-                log_to_file("joined listener", &shared_logger)?;
+                shared_logger.debug("joined listener");
             }
             Err(_) => {
                 return Err(NodeSpaceError::SocketError(
@@ -113,7 +214,21 @@ impl Coordinator {
 
         match thread_handle_file_change.join() {
             Ok(_) => {
-                log_to_file("joined file handle thread", &shared_logger)?;
+                shared_logger.debug("joined file handle thread");
+            }
+            Err(_) => {
+                return Err(NodeSpaceError::SocketError(
+                    SocketError::ErrorConnectingToSocket("error closing thread".to_string()),
+                ));
+            }
+        };
+
+        shared_logger.debug("send stop to build scheduler");
+        let _ = scheduler_sender.send(SchedulerMessage::Stop);
+
+        match thread_handle_scheduler.join() {
+            Ok(_) => {
+                shared_logger.debug("joined build scheduler thread");
             }
             Err(_) => {
                 return Err(NodeSpaceError::SocketError(
@@ -122,7 +237,11 @@ impl Coordinator {
             }
         };
 
-        log_to_file("threads closed", &shared_logger)?;
+        shared_logger.info("Coordinator stopped");
+
+        pid_manager.delete_pid()?;
+
+        shared_logger.debug("removed pid file");
 
         Ok(true)
     }
@@ -0,0 +1,527 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, BufReader, Read},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use crate::{
+    cache::input_hash::get_input_hash,
+    command_line::{
+        node_build::{handle_command_result, spawn_node_command},
+        process::terminate_process_group,
+    },
+    time_utils::get_unix_timestamp,
+    watch_coordinator::coordinator::{
+        build_log_utils::{create_build_log_file, write_build_log_line, BuildLogFile},
+        log_utils::CoordinatorLogger,
+    },
+};
+
+use super::{
+    build_cache::BuildCache,
+    build_result::BuildResult,
+    coordinator::Coordinator,
+    coordinator_event::{BuildOutputStream, CoordinatorEvent},
+    coordinator_subscribers::CoordinatorSubscribers,
+    package::Package,
+};
+
+struct RunningBuild {
+    pid: u32,
+    started_at: Instant,
+    /// recorded in the build cache once the build succeeds
+    input_hash: String,
+}
+
+pub enum SchedulerMessage {
+    /// projects to build ordered dependencies first, with the dependencies of each project
+    Build(Vec<String>, HashMap<String, Vec<String>>),
+    Finished(String, bool),
+    Stop,
+}
+
+pub type SchedulerSender = Sender<SchedulerMessage>;
+
+/// copy every line the build writes to the project build log and to the subscribed clients
+fn forward_build_output<R: Read + Send + 'static>(
+    output: R,
+    path: String,
+    stream: BuildOutputStream,
+    build_log: Option<BuildLogFile>,
+    subscribers: CoordinatorSubscribers,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(output);
+        let mut buffer = Vec::new();
+
+        loop {
+            buffer.clear();
+
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            };
+
+            let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+
+            if let Some(build_log) = &build_log {
+                let _ = write_build_log_line(&line, build_log);
+            }
+
+            subscribers.broadcast(&CoordinatorEvent::BuildOutput {
+                path: path.clone(),
+                stream,
+                line,
+            });
+        }
+    })
+}
+
+pub fn get_default_max_jobs() -> usize {
+    match thread::available_parallelism() {
+        Ok(value) => value.get(),
+        Err(_) => 1,
+    }
+}
+
+/// runs project builds on worker threads, a project starts only after every dependency that is
+/// queued or running has finished, independent projects build in parallel up to `max_jobs`
+/// a project is queued at most once, and a running build is killed and queued again when a newer
+/// change asks to build the same project
+pub struct CoordinatorBuildScheduler {
+    max_jobs: usize,
+    queue: Vec<String>,
+    /// project path -> the running `npm run build`
+    running: HashMap<String, RunningBuild>,
+    cancelled: HashSet<String>,
+    dependencies: HashMap<String, Vec<String>>,
+    subscribers: CoordinatorSubscribers,
+}
+
+impl CoordinatorBuildScheduler {
+    pub fn new(max_jobs: usize, subscribers: CoordinatorSubscribers) -> Self {
+        Self {
+            max_jobs: max_jobs.max(1),
+            queue: Vec::new(),
+            running: HashMap::new(),
+            cancelled: HashSet::new(),
+            dependencies: HashMap::new(),
+            subscribers,
+        }
+    }
+
+    fn enqueue(
+        &mut self,
+        paths: Vec<String>,
+        dependencies: HashMap<String, Vec<String>>,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        self.dependencies = dependencies;
+
+        for path in paths {
+            self.cancel(&path, shared_logger);
+
+            if !self.queue.contains(&path) {
+                self.queue.push(path);
+            }
+        }
+    }
+
+    /// kill the running build of `path`, its `Finished` message is not treated as a failure
+    fn cancel(&mut self, path: &str, shared_logger: &CoordinatorLogger) {
+        let pid = match self.running.get(path) {
+            Some(value) => value.pid,
+            None => return,
+        };
+
+        if !self.cancelled.insert(path.to_string()) {
+            return;
+        }
+
+        shared_logger.with_project(path).info("cancelling build");
+
+        if let Err(error) = terminate_process_group(pid) {
+            shared_logger
+                .with_project(path)
+                .error(&format!("{}", error));
+        }
+    }
+
+    fn is_ready(&self, path: &str) -> bool {
+        if self.running.contains_key(path) {
+            return false;
+        }
+
+        let dependencies = match self.dependencies.get(path) {
+            Some(value) => value,
+            None => return true,
+        };
+
+        !dependencies.iter().any(|dependency| {
+            self.queue.contains(dependency) || self.running.contains_key(dependency)
+        })
+    }
+
+    /// drop every queued project that depends on `path`, directly or not
+    fn skip_dependents(
+        &mut self,
+        path: &str,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        let mut failed = vec![path.to_string()];
+
+        while let Some(current) = failed.pop() {
+            let dependents = self
+                .queue
+                .iter()
+                .filter(|queued| match self.dependencies.get(*queued) {
+                    Some(value) => value.contains(&current),
+                    None => false,
+                })
+                .cloned()
+                .collect::<Vec<String>>();
+
+            for dependent in dependents {
+                shared_logger
+                    .with_project(&dependent)
+                    .warn("skipping build, dependency failed");
+
+                self.queue.retain(|queued| queued != &dependent);
+
+                self.subscribers.broadcast(&CoordinatorEvent::BuildSkipped {
+                    path: dependent.clone(),
+                });
+
+                Self::settle_content_hashes(coordinator_lock, &dependent, false, shared_logger);
+
+                failed.push(dependent);
+            }
+        }
+    }
+
+    /// the project as registered and the hash of its inputs, a project the coordinator doesn't know
+    /// anymore is hashed with its defaults
+    fn get_build_input(
+        &self,
+        path: &str,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        shared_logger: &CoordinatorLogger,
+    ) -> (Package, String) {
+        let (project, dependencies) = match coordinator_lock.lock() {
+            Ok(coordinator) => {
+                let find_package = |package_path: &str| {
+                    coordinator
+                        .watchers_target
+                        .iter()
+                        .chain(coordinator.registered_projects.iter())
+                        .find(|package| package.path == package_path)
+                        .cloned()
+                };
+
+                let project = find_package(path)
+                    .unwrap_or_else(|| Package::new(path.to_string(), String::new(), None, None));
+
+                let dependencies = match self.dependencies.get(path) {
+                    Some(value) => value
+                        .iter()
+                        .filter_map(|dependency| find_package(dependency))
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                (project, dependencies)
+            }
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
+
+                (
+                    Package::new(path.to_string(), String::new(), None, None),
+                    Vec::new(),
+                )
+            }
+        };
+
+        let input_hash = get_input_hash(&project, &dependencies);
+
+        (project, input_hash)
+    }
+
+    fn start_ready_builds(
+        &mut self,
+        sender: &SchedulerSender,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        while self.running.len() < self.max_jobs {
+            let next = self.queue.iter().position(|path| self.is_ready(path));
+
+            let path = match next {
+                Some(index) => self.queue.remove(index),
+                None => break,
+            };
+
+            let (project, input_hash) =
+                self.get_build_input(&path, coordinator_lock, shared_logger);
+
+            let is_up_to_date = match BuildCache::new() {
+                Ok(cache) => cache.is_up_to_date(&project, &input_hash),
+                Err(_) => false,
+            };
+
+            if is_up_to_date {
+                shared_logger
+                    .with_project(&path)
+                    .info("inputs unchanged since the last build, skipping build");
+
+                self.finish(&path, true, coordinator_lock, shared_logger);
+
+                continue;
+            }
+
+            shared_logger.with_project(&path).info("running build");
+
+            self.subscribers
+                .broadcast(&CoordinatorEvent::BuildStarted { path: path.clone() });
+
+            let mut child = match spawn_node_command(&path, "build") {
+                Ok(value) => value,
+                Err(error) => {
+                    shared_logger
+                        .with_project(&path)
+                        .error(&format!("error building project: {}", error));
+
+                    self.finish(&path, false, coordinator_lock, shared_logger);
+
+                    continue;
+                }
+            };
+
+            self.running.insert(
+                path.clone(),
+                RunningBuild {
+                    pid: child.id(),
+                    started_at: Instant::now(),
+                    input_hash,
+                },
+            );
+
+            let build_log = match create_build_log_file(&path) {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    shared_logger
+                        .with_project(&path)
+                        .warn(&format!("{}", error));
+
+                    None
+                }
+            };
+
+            let mut output_handles = Vec::new();
+
+            if let Some(stdout) = child.stdout.take() {
+                output_handles.push(forward_build_output(
+                    stdout,
+                    path.clone(),
+                    BuildOutputStream::Stdout,
+                    build_log.clone(),
+                    self.subscribers.clone(),
+                ));
+            }
+
+            if let Some(stderr) = child.stderr.take() {
+                output_handles.push(forward_build_output(
+                    stderr,
+                    path.clone(),
+                    BuildOutputStream::Stderr,
+                    build_log,
+                    self.subscribers.clone(),
+                ));
+            }
+
+            let sender = sender.clone();
+            let shared_logger = shared_logger.clone();
+
+            thread::spawn(move || {
+                let success = match handle_command_result(Ok(child)) {
+                    Ok(_) => true,
+                    // the scheduler logs the outcome, a cancelled build also ends here
+                    Err(error) => {
+                        shared_logger
+                            .with_project(&path)
+                            .debug(&format!("build command exited: {}", error));
+
+                        false
+                    }
+                };
+
+                for handle in output_handles {
+                    let _ = handle.join();
+                }
+
+                let _ = sender.send(SchedulerMessage::Finished(path, success));
+            });
+        }
+    }
+
+    fn record_result(
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        path: &str,
+        result: BuildResult,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        match coordinator_lock.lock() {
+            Ok(mut coordinator) => {
+                coordinator.last_builds.insert(path.to_string(), result);
+            }
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
+            }
+        };
+    }
+
+    /// keep the content hashes of a successful build, drop them otherwise
+    fn settle_content_hashes(
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        path: &str,
+        success: bool,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        let mut coordinator = match coordinator_lock.lock() {
+            Ok(value) => value,
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
+
+                return;
+            }
+        };
+
+        if let Some(content_hashes) = coordinator.content_hashes.get_mut(path) {
+            match success {
+                true => content_hashes.commit(),
+                false => content_hashes.discard(),
+            }
+        }
+    }
+
+    fn finish(
+        &mut self,
+        path: &str,
+        success: bool,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        let running_build = self.running.remove(path);
+
+        if self.cancelled.remove(path) {
+            shared_logger.with_project(path).info("cancelled build");
+
+            Self::settle_content_hashes(coordinator_lock, path, false, shared_logger);
+
+            self.subscribers
+                .broadcast(&CoordinatorEvent::BuildCancelled {
+                    path: path.to_string(),
+                });
+
+            return;
+        }
+
+        // a build skipped by the cache was never running and already logged
+        match (success, running_build.is_some()) {
+            (true, true) => shared_logger.with_project(path).info("finished build"),
+            (true, false) => {}
+            (false, _) => shared_logger.with_project(path).warn("build failed"),
+        };
+
+        let duration_ms = match &running_build {
+            Some(value) => value.started_at.elapsed().as_millis() as u64,
+            None => 0,
+        };
+
+        if let (true, Some(value)) = (success, &running_build) {
+            if let Err(error) = BuildCache::record(path, &value.input_hash) {
+                shared_logger.with_project(path).warn(&error.to_string());
+            }
+        }
+
+        let result = BuildResult::new(success, get_unix_timestamp(), duration_ms);
+
+        self.subscribers
+            .broadcast(&CoordinatorEvent::BuildFinished {
+                path: path.to_string(),
+                result: result.clone(),
+            });
+
+        Self::record_result(coordinator_lock, path, result, shared_logger);
+        Self::settle_content_hashes(coordinator_lock, path, success, shared_logger);
+
+        if !success {
+            self.skip_dependents(path, coordinator_lock, shared_logger);
+        }
+    }
+
+    fn run(
+        mut self,
+        sender: SchedulerSender,
+        reciver: Receiver<SchedulerMessage>,
+        coordinator_lock: Arc<Mutex<Coordinator>>,
+        shared_logger: CoordinatorLogger,
+    ) {
+        let mut is_stopping = false;
+
+        for message in reciver.iter() {
+            match message {
+                SchedulerMessage::Build(paths, dependencies) => {
+                    if !is_stopping {
+                        self.enqueue(paths, dependencies, &shared_logger);
+                    }
+                }
+                SchedulerMessage::Finished(path, success) => {
+                    self.finish(&path, success, &coordinator_lock, &shared_logger);
+                }
+                SchedulerMessage::Stop => {
+                    shared_logger.debug("Terminating build scheduler.");
+
+                    is_stopping = true;
+                    self.queue.clear();
+
+                    let running = self.running.keys().cloned().collect::<Vec<String>>();
+
+                    for path in running {
+                        self.cancel(&path, &shared_logger);
+                    }
+                }
+            }
+
+            if is_stopping {
+                if self.running.is_empty() {
+                    break;
+                }
+
+                continue;
+            }
+
+            self.start_ready_builds(&sender, &coordinator_lock, &shared_logger);
+        }
+    }
+
+    pub fn start(
+        self,
+        coordinator_lock: Arc<Mutex<Coordinator>>,
+        shared_logger: CoordinatorLogger,
+    ) -> (SchedulerSender, JoinHandle<()>) {
+        let (sender, reciver) = channel();
+        let worker_sender = sender.clone();
+
+        let handle = thread::spawn(move || {
+            self.run(worker_sender, reciver, coordinator_lock, shared_logger)
+        });
+
+        (sender, handle)
+    }
+}
@@ -0,0 +1,11 @@
+use std::sync::mpsc::Sender;
+
+/// messages for the coordinator main thread, it blocks on them instead of polling the shared state
+pub enum ControlMessage {
+    /// the watched targets changed, add and remove file watchers to match them
+    SyncWatchers,
+    /// stop the coordinator, with the reason for the log
+    Stop(String),
+}
+
+pub type ControlSender = Sender<ControlMessage>;
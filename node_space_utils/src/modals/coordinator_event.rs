@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::build_result::BuildResult;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildOutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// written as a json line to every client that sent a `subscribe` request
+/// ```json
+/// {"type": "build_output", "data": {"path": "/home/user/dev/package1", "stream": "stdout", "line": "done"}}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum CoordinatorEvent {
+    BuildStarted {
+        path: String,
+    },
+    BuildOutput {
+        path: String,
+        stream: BuildOutputStream,
+        line: String,
+    },
+    BuildFinished {
+        path: String,
+        result: BuildResult,
+    },
+    /// a newer change restarted the build or the coordinator is stopping
+    BuildCancelled {
+        path: String,
+    },
+    /// a dependency of the project failed to build
+    BuildSkipped {
+        path: String,
+    },
+}
+
+impl CoordinatorEvent {
+    pub fn get_path(&self) -> &str {
+        match self {
+            CoordinatorEvent::BuildStarted { path } => path,
+            CoordinatorEvent::BuildOutput { path, .. } => path,
+            CoordinatorEvent::BuildFinished { path, .. } => path,
+            CoordinatorEvent::BuildCancelled { path } => path,
+            CoordinatorEvent::BuildSkipped { path } => path,
+        }
+    }
+}
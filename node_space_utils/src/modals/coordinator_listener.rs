@@ -1,17 +1,31 @@
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Write},
     os::unix::net::{UnixListener, UnixStream},
     sync::{mpsc::Receiver, Arc, Mutex},
     thread::{self, JoinHandle},
+    time::Duration,
 };
 
+use serde_json::Value;
+
 use crate::watch_coordinator::coordinator::{
-    log_utils::{log_to_file, LogFile},
-    process_stream_request::process_stream_request,
+    log_utils::CoordinatorLogger,
+    process_stream_request::{detach_project, process_stream_request},
     socket_file::delete_socket_file,
 };
 
-use super::coordinator::Coordinator;
+use super::{
+    coordinator::Coordinator,
+    coordinator_build_scheduler::SchedulerSender,
+    coordinator_control::ControlSender,
+    coordinator_message::{
+        CoordinatorRequest, CoordinatorRequestMessage, CoordinatorResponse,
+        CoordinatorResponseError, COORDINATOR_PROTOCOL_VERSION,
+    },
+    coordinator_subscribers::CoordinatorSubscribers,
+};
+
+const REQUEST_READ_TIMEOUT: Option<Duration> = Some(Duration::from_secs(5));
 
 pub struct CoordinatorListener;
 
@@ -20,57 +34,221 @@ impl CoordinatorListener {
         Self
     }
 
+    /// the version is checked before the message shape so that clients using a newer protocol get
+    /// `UnsupportedVersion` instead of a parse error
+    fn parse_request(data_str: &str) -> Result<CoordinatorRequest, CoordinatorResponseError> {
+        let value: Value = match serde_json::from_str(data_str) {
+            Ok(value) => value,
+            Err(error) => return Err(CoordinatorResponseError::InvalidRequest(error.to_string())),
+        };
+
+        let version = match value.get("version").and_then(Value::as_u64) {
+            Some(value) => value as u32,
+            None => {
+                return Err(CoordinatorResponseError::InvalidRequest(String::from(
+                    "missing protocol version",
+                )))
+            }
+        };
+
+        if version != COORDINATOR_PROTOCOL_VERSION {
+            return Err(CoordinatorResponseError::UnsupportedVersion(version));
+        }
+
+        match serde_json::from_value::<CoordinatorRequestMessage>(value) {
+            Ok(message) => Ok(message.request),
+            Err(error) => Err(CoordinatorResponseError::InvalidRequest(error.to_string())),
+        }
+    }
+
+    fn write_response(
+        mut stream: &UnixStream,
+        response: &CoordinatorResponse,
+        shared_logger: &CoordinatorLogger,
+    ) -> bool {
+        let response_str = match serde_json::to_string(response) {
+            Ok(value) => value,
+            Err(error) => {
+                shared_logger.error(&format!("failed to serialize response: {}", error));
+
+                return false;
+            }
+        };
+
+        if let Err(error) = writeln!(stream, "{}", response_str) {
+            shared_logger.warn(&format!("failed to write response: {}", error));
+
+            return false;
+        }
+
+        true
+    }
+
     fn handle_request(
         coordinator_lock: &Arc<Mutex<Coordinator>>,
         stream: UnixStream,
-        shared_logger: &LogFile,
+        scheduler: &SchedulerSender,
+        control: &ControlSender,
+        subscribers: &CoordinatorSubscribers,
+        shared_logger: &CoordinatorLogger,
     ) {
+        let _ = stream.set_read_timeout(REQUEST_READ_TIMEOUT);
+
         let mut reader = BufReader::new(&stream);
         let mut data_str = String::new();
 
         if reader.read_line(&mut data_str).is_err() {
-            let _ = log_to_file("failed to read data from stream", &shared_logger);
+            shared_logger.warn("failed to read data from stream");
 
             return;
         }
 
         if data_str.is_empty() {
-            let _ = log_to_file("got empty data", &shared_logger);
+            shared_logger.debug("got empty data");
+
+            return;
+        }
+
+        let request = match Self::parse_request(&data_str) {
+            Ok(value) => value,
+            Err(error) => {
+                shared_logger.warn(&format!(
+                    "invalid request: {}, data: {}",
+                    error,
+                    data_str.trim_end()
+                ));
+
+                Self::write_response(&stream, &CoordinatorResponse::Error(error), shared_logger);
+
+                return;
+            }
+        };
+
+        let is_subscribe = matches!(request, CoordinatorRequest::Subscribe);
+
+        let attached_path = match &request {
+            CoordinatorRequest::Attach { path } => Some(path.clone()),
+            _ => None,
+        };
+
+        let response =
+            process_stream_request(coordinator_lock, request, scheduler, control, shared_logger);
+
+        if !Self::write_response(&stream, &response, shared_logger) {
+            return;
+        }
+
+        if is_subscribe {
+            let _ = stream.set_read_timeout(None);
+
+            subscribers.add(stream);
 
             return;
         }
 
-        process_stream_request(&coordinator_lock, &data_str, shared_logger);
+        if let (true, Some(path)) = (response.is_accepted(), attached_path) {
+            Self::watch_attached_client(
+                Arc::clone(coordinator_lock),
+                stream,
+                path,
+                scheduler.clone(),
+                control.clone(),
+                shared_logger.clone(),
+            );
+        }
+    }
+
+    /// wait until the client that attached to `path` closes the stream, the project is unwatched
+    /// once no other client is attached to it
+    fn watch_attached_client(
+        coordinator_lock: Arc<Mutex<Coordinator>>,
+        stream: UnixStream,
+        path: String,
+        scheduler: SchedulerSender,
+        control: ControlSender,
+        shared_logger: CoordinatorLogger,
+    ) {
+        let _ = stream.set_read_timeout(None);
+
+        thread::spawn(move || {
+            let mut reader = BufReader::new(&stream);
+            let mut buffer = String::new();
+
+            // the client never writes after attaching, any read returning means it is gone
+            while let Ok(size) = reader.read_line(&mut buffer) {
+                if size == 0 {
+                    break;
+                }
+
+                buffer.clear();
+            }
+
+            let is_last = match coordinator_lock.lock() {
+                Ok(mut coordinator) => detach_project(&mut coordinator, &path),
+                Err(_) => false,
+            };
+
+            if !is_last {
+                shared_logger
+                    .with_project(&path)
+                    .info("client closed, other clients are still attached");
+
+                return;
+            }
+
+            shared_logger
+                .with_project(&path)
+                .info("client closed, unwatching project");
+
+            process_stream_request(
+                &coordinator_lock,
+                CoordinatorRequest::Unwatch { path },
+                &scheduler,
+                &control,
+                &shared_logger,
+            );
+        });
     }
 
     pub fn handle_listener(
         listener: UnixListener,
         coordinator_lock: Arc<Mutex<Coordinator>>,
+        scheduler: SchedulerSender,
+        control: ControlSender,
+        subscribers: CoordinatorSubscribers,
         kill_thread_reciever: Receiver<String>,
-        shared_logger: LogFile,
+        shared_logger: CoordinatorLogger,
     ) -> JoinHandle<()> {
         let handler = thread::spawn(move || {
-            let _ = log_to_file("handle_listener", &shared_logger);
+            shared_logger.debug("handle_listener");
 
-            loop {
+            // accept blocks, on shutdown the coordinator connects to the socket to wake it up
+            for accept_data in listener.incoming() {
                 let kill_massage = kill_thread_reciever.try_recv();
 
                 if let Ok(message) = kill_massage {
-                    let _ = log_to_file("Terminating.", &shared_logger);
-                    let _ = log_to_file(&message, &shared_logger);
+                    shared_logger.debug(&format!("Terminating listener: {}", message));
 
                     break;
                 }
 
-                let accept_data = listener.accept();
-
-                if accept_data.is_err() {
-                    continue;
-                }
+                let stream = match accept_data {
+                    Ok(value) => value,
+                    Err(error) => {
+                        shared_logger.warn(&format!("failed to accept connection: {}", error));
 
-                let (stream, _) = accept_data.unwrap();
+                        continue;
+                    }
+                };
 
-                Self::handle_request(&coordinator_lock, stream, &shared_logger);
+                Self::handle_request(
+                    &coordinator_lock,
+                    stream,
+                    &scheduler,
+                    &control,
+                    &subscribers,
+                    &shared_logger,
+                );
             }
 
             drop(listener);
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use super::{build_result::BuildResult, package::Package, socket_build_data::SocketBuildData};
+
+/// bumped on every breaking change of the socket messages
+pub const COORDINATOR_PROTOCOL_VERSION: u32 = 1;
+
+/// every message sent to the coordinator is a single json line:
+/// ```json
+/// {"version": 1, "type": "trigger_build", "data": {"path": "/home/user/dev/package1"}}
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoordinatorRequestMessage {
+    pub version: u32,
+
+    #[serde(flatten)]
+    pub request: CoordinatorRequest,
+}
+
+impl CoordinatorRequestMessage {
+    pub fn new(request: CoordinatorRequest) -> Self {
+        Self {
+            version: COORDINATOR_PROTOCOL_VERSION,
+            request,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum CoordinatorRequest {
+    Register(Box<SocketBuildData>),
+    /// remove the project and the dependencies that no other project needs
+    #[serde(alias = "unregister")]
+    Unwatch {
+        path: String,
+    },
+    /// keep the stream open while the client runs, the project is unwatched when it is closed
+    Attach {
+        path: String,
+    },
+    Status,
+    List,
+    Stop,
+    TriggerBuild {
+        path: String,
+    },
+    /// keep the stream open and receive coordinator events on it
+    Subscribe,
+    /// health check, answered with `pong`
+    Ping,
+}
+
+/// fields added after the first protocol version default when missing, so a status from an older
+/// coordinator still parses
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoordinatorStatus {
+    pub pid: u32,
+    #[serde(default)]
+    pub instance: String,
+    #[serde(default)]
+    pub max_jobs: usize,
+    #[serde(default)]
+    /// unix timestamp in seconds
+    pub started_at: u64,
+    #[serde(default)]
+    pub uptime_seconds: u64,
+    #[serde(default)]
+    pub registered_projects: Vec<Package>,
+    #[serde(default)]
+    pub registration_owners: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub attached_projects: Vec<String>,
+    pub watchers_target: Vec<Package>,
+    pub active_watchers: Vec<String>,
+    #[serde(default)]
+    pub projects_to_dependencies_map: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub last_builds: HashMap<String, BuildResult>,
+}
+
+impl CoordinatorStatus {
+    /// rebuild the register requests that produced this status, so they can be sent again to a
+    /// new coordinator, one for every owner of a project. registrations kept by a `build --watch`
+    /// stream are left out, the stream does not survive the restart
+    pub fn get_registrations(&self) -> Vec<SocketBuildData> {
+        self.registered_projects
+            .iter()
+            .flat_map(|project| {
+                let symlinks = match self.projects_to_dependencies_map.get(&project.path) {
+                    Some(dependencies) => self
+                        .watchers_target
+                        .iter()
+                        .filter(|package| dependencies.contains(&package.path))
+                        .cloned()
+                        .collect::<Vec<Package>>(),
+                    None => Vec::new(),
+                };
+
+                let watch_only_links = !self
+                    .watchers_target
+                    .iter()
+                    .any(|package| package.path == project.path);
+
+                let data = SocketBuildData::new(symlinks, project.clone(), watch_only_links);
+
+                let owners = match self.registration_owners.get(&project.path) {
+                    Some(value) if !value.is_empty() => value.clone(),
+                    _ => vec![project.path.clone()],
+                };
+
+                owners
+                    .into_iter()
+                    .filter(|owner| !self.attached_projects.contains(owner))
+                    .map(|owner| match owner == project.path {
+                        true => data.clone(),
+                        false => data.clone().with_owner(&owner),
+                    })
+                    .collect::<Vec<SocketBuildData>>()
+            })
+            .collect()
+    }
+}
+
+/// the coordinator writes back exactly one json line for every request
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum CoordinatorResponse {
+    Accepted,
+    Status(Box<CoordinatorStatus>),
+    List(Vec<Package>),
+    Subscribed,
+    Pong { pid: u32 },
+    Error(CoordinatorResponseError),
+}
+
+impl CoordinatorResponse {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, CoordinatorResponse::Accepted)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum CoordinatorResponseError {
+    UnsupportedVersion(u32),
+    InvalidRequest(String),
+    UnknownProject(String),
+    Internal(String),
+}
+
+impl fmt::Display for CoordinatorResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CoordinatorResponseError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "unsupported protocol version: {}, coordinator version is: {}",
+                    version, COORDINATOR_PROTOCOL_VERSION
+                )
+            }
+            CoordinatorResponseError::InvalidRequest(ref message) => {
+                write!(f, "invalid request: {}", message)
+            }
+            CoordinatorResponseError::UnknownProject(ref message) => {
+                write!(f, "project is not registered: {}", message)
+            }
+            CoordinatorResponseError::Internal(ref message) => {
+                write!(f, "coordinator error: {}", message)
+            }
+        }
+    }
+}
@@ -1,9 +1,7 @@
 use std::fs;
-use std::path::Path;
 
 use crate::errors::node_space::NodeSpaceError;
-
-pub const COORDINATOR_PID_PATH: &str = "/tmp/node-space-coordinator.pid";
+use crate::watch_coordinator::runtime_dir::get_coordinator_pid_path;
 
 #[derive(Copy, Clone)]
 pub struct CoordinatorPIDManager;
@@ -14,11 +12,11 @@ impl CoordinatorPIDManager {
     }
 
     pub fn is_exists(&self) -> bool {
-        Path::new(COORDINATOR_PID_PATH).exists()
+        get_coordinator_pid_path().exists()
     }
 
     pub fn read_pid(&self) -> Result<u32, NodeSpaceError> {
-        match fs::read_to_string(COORDINATOR_PID_PATH) {
+        match fs::read_to_string(get_coordinator_pid_path()) {
             Ok(value) => match value.trim().parse::<u32>() {
                 Ok(number_value) => Ok(number_value),
                 Err(error) => Err(NodeSpaceError::CantParsePIDNumber(error.to_string())),
@@ -28,7 +26,18 @@ impl CoordinatorPIDManager {
     }
 
     pub fn write_pid(&self, pid: u32) -> Result<(), NodeSpaceError> {
-        match fs::write(COORDINATOR_PID_PATH, pid.to_string()) {
+        match fs::write(get_coordinator_pid_path(), pid.to_string()) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(NodeSpaceError::CantWriteToPIDFile(error.to_string())),
+        }
+    }
+
+    pub fn delete_pid(&self) -> Result<(), NodeSpaceError> {
+        if !self.is_exists() {
+            return Ok(());
+        }
+
+        match fs::remove_file(get_coordinator_pid_path()) {
             Ok(_) => Ok(()),
             Err(error) => Err(NodeSpaceError::CantWriteToPIDFile(error.to_string())),
         }
@@ -0,0 +1,101 @@
+use std::{collections::HashMap, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::node_space::NodeSpaceError, watch_coordinator::runtime_dir::get_coordinator_state_path,
+};
+
+use super::{coordinator::Coordinator, package::Package};
+
+/// the registrations of the coordinator, written to the instance dir on every change so a
+/// coordinator that crashed can pick up where it stopped
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CoordinatorState {
+    pub registered_projects: Vec<Package>,
+    #[serde(default)]
+    pub registration_owners: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    /// projects kept by a `build --watch` stream, the stream is gone once the state is loaded
+    pub attached_projects: Vec<String>,
+    pub watchers_target: Vec<Package>,
+    pub dependencies_to_projects_map: HashMap<String, Vec<String>>,
+    pub projects_to_dependencies_map: HashMap<String, Vec<String>>,
+}
+
+impl CoordinatorState {
+    pub fn from_coordinator(coordinator: &Coordinator) -> Self {
+        CoordinatorState {
+            registered_projects: coordinator.registered_projects.clone(),
+            registration_owners: coordinator.registration_owners.clone(),
+            attached_projects: coordinator.attached_projects.keys().cloned().collect(),
+            watchers_target: coordinator.watchers_target.clone(),
+            dependencies_to_projects_map: coordinator.dependencies_to_projects_map.clone(),
+            projects_to_dependencies_map: coordinator.projects_to_dependencies_map.clone(),
+        }
+    }
+
+    /// replace the registrations of the coordinator, watchers are added on the next sync.
+    /// projects saved without owners were registered by their own client, no stream is attached
+    /// to a coordinator that was just started
+    pub fn apply(self, coordinator: &mut Coordinator) {
+        let mut registration_owners = self.registration_owners;
+
+        for project in self.registered_projects.iter() {
+            registration_owners
+                .entry(project.path.clone())
+                .or_insert_with(|| vec![project.path.clone()]);
+        }
+
+        coordinator.registered_projects = self.registered_projects;
+        coordinator.registration_owners = registration_owners;
+        coordinator.attached_projects.clear();
+        coordinator.watchers_target = self.watchers_target;
+        coordinator.dependencies_to_projects_map = self.dependencies_to_projects_map;
+        coordinator.projects_to_dependencies_map = self.projects_to_dependencies_map;
+    }
+
+    /// written to a temporary file first and renamed, a crash while saving keeps the old state
+    pub fn save(&self) -> Result<(), NodeSpaceError> {
+        let state_path = get_coordinator_state_path();
+
+        let mut temp_path = state_path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        let json_data = match serde_json::to_string_pretty(self) {
+            Ok(value) => value,
+            Err(error) => return Err(NodeSpaceError::CantSaveCoordinatorState(error.to_string())),
+        };
+
+        if let Err(error) = fs::write(&temp_path, json_data) {
+            return Err(NodeSpaceError::CantSaveCoordinatorState(error.to_string()));
+        }
+
+        match fs::rename(&temp_path, &state_path) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(NodeSpaceError::CantSaveCoordinatorState(error.to_string())),
+        }
+    }
+
+    /// `None` when there is no saved state
+    pub fn load() -> Result<Option<Self>, NodeSpaceError> {
+        let json_data = match fs::read_to_string(get_coordinator_state_path()) {
+            Ok(value) => value,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(NodeSpaceError::CantLoadCoordinatorState(error.to_string())),
+        };
+
+        match serde_json::from_str::<CoordinatorState>(&json_data) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => Err(NodeSpaceError::CantLoadCoordinatorState(error.to_string())),
+        }
+    }
+
+    pub fn delete() -> Result<(), NodeSpaceError> {
+        match fs::remove_file(get_coordinator_state_path()) {
+            Ok(_) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(NodeSpaceError::CantSaveCoordinatorState(error.to_string())),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    io::Write,
+    os::unix::net::UnixStream,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::Serialize;
+
+const SUBSCRIBER_WRITE_TIMEOUT: Option<Duration> = Some(Duration::from_secs(1));
+
+/// streams of the clients that sent a `subscribe` request, every broadcast is written to all of
+/// them as a json line and closed streams are dropped
+#[derive(Clone, Default)]
+pub struct CoordinatorSubscribers {
+    streams: Arc<Mutex<Vec<UnixStream>>>,
+}
+
+impl CoordinatorSubscribers {
+    pub fn new() -> Self {
+        Self {
+            streams: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// a client that stops reading is dropped once its socket buffer is full instead of blocking
+    /// the builds
+    pub fn add(&self, stream: UnixStream) {
+        let _ = stream.set_write_timeout(SUBSCRIBER_WRITE_TIMEOUT);
+
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.push(stream);
+        }
+    }
+
+    pub fn broadcast<T: Serialize>(&self, message: &T) {
+        let message_str = match serde_json::to_string(message) {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+
+        let mut streams = match self.streams.lock() {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+
+        streams.retain_mut(|stream| writeln!(stream, "{}", message_str).is_ok());
+    }
+}
@@ -1,18 +1,36 @@
 use std::{
+    collections::HashMap,
     fs,
-    sync::{mpsc::Receiver, Arc, Mutex, MutexGuard},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{Receiver, RecvTimeoutError},
+        Arc, Mutex, MutexGuard,
+    },
     thread::{self, JoinHandle},
+    time::Instant,
 };
 
-use notify::{Error, EventKind};
+use notify::Error;
 use notify_debouncer_full::DebouncedEvent;
 
 use crate::{
-    command_line::node_build::run_node_command,
-    watch_coordinator::coordinator::log_utils::{log_to_file, LogFile},
+    watch_coordinator::coordinator::{
+        dependency_graph::get_build_order, log_utils::CoordinatorLogger,
+    },
+    watch_filter::WatchFilter,
 };
 
-use super::{coordinator::Coordinator, unique_vec::UniqueVec};
+use super::{
+    coordinator::Coordinator,
+    coordinator_build_scheduler::{SchedulerMessage, SchedulerSender},
+    package::Package,
+};
+
+/// changes of a project waiting for its debounce interval to pass
+struct PendingChanges {
+    deadline: Instant,
+    paths: Vec<PathBuf>,
+}
 
 pub struct CoordinatorUpdatesManager;
 
@@ -21,189 +39,247 @@ impl CoordinatorUpdatesManager {
         Self
     }
 
+    /// removed files can't be canonicalized, their path is used as it is
+    fn get_event_path(path: &Path, shared_logger: &CoordinatorLogger) -> PathBuf {
+        match fs::canonicalize(path) {
+            Ok(value) => value,
+            Err(_) => {
+                shared_logger.debug(&format!(
+                    "couldn't convert path to canonical, {}",
+                    path.display()
+                ));
+
+                path.to_path_buf()
+            }
+        }
+    }
+
+    /// every change pushes the deadline of the project back by its debounce interval
     fn handle_event(
-        paths: &mut UniqueVec<String>,
+        pending: &mut HashMap<String, PendingChanges>,
         event: &DebouncedEvent,
-        shared_logger: &LogFile,
-        coordinator: &MutexGuard<'_, Coordinator>,
+        shared_logger: &CoordinatorLogger,
+        targets: &[(Package, WatchFilter)],
     ) {
-        let event_paths = event
-            .paths
-            .iter()
-            .map(|path| {
-                let canonical_path = fs::canonicalize(path);
+        for path in event.paths.iter() {
+            let current_event_path = Self::get_event_path(path, shared_logger);
 
-                if canonical_path.is_err() {
-                    let path_error = match path.to_str() {
-                        Some(value) => value,
-                        None => "",
-                    };
+            shared_logger.debug(&format!(
+                "got change events: {}",
+                current_event_path.display()
+            ));
 
-                    let _ = log_to_file(
-                        &format!("couldn't convert path to canonical, {}", path_error),
-                        shared_logger,
-                    );
+            for (watched_path, filter) in targets.iter() {
+                if !watched_path.is_triggered_by(&event.kind) {
+                    continue;
+                }
 
-                    return "".to_string();
+                // covers the output folder, ignored files and the include and exclude globs
+                if !filter.is_relevant(&current_event_path) {
+                    continue;
                 }
 
-                let canonical_path_buf = canonical_path.unwrap();
-                let canonical_path = canonical_path_buf.to_str();
+                let deadline = Instant::now() + watched_path.get_debounce();
 
-                if canonical_path.is_none() {
-                    let _ = log_to_file("couldn't convert path to string", shared_logger);
+                let changes =
+                    pending
+                        .entry(watched_path.path.clone())
+                        .or_insert_with(|| PendingChanges {
+                            deadline,
+                            paths: Vec::new(),
+                        });
 
-                    return "".to_string();
+                changes.deadline = deadline;
+
+                if !changes.paths.contains(&current_event_path) {
+                    changes.paths.push(current_event_path.clone());
                 }
+            }
+        }
+    }
 
-                let current_path = canonical_path.unwrap();
+    /// the `.gitignore` and package.json files are read once per batch of events so edits to
+    /// them apply without a restart, packages linked into a project only watch what they publish
+    fn get_watch_filters(coordinator: &MutexGuard<'_, Coordinator>) -> Vec<(Package, WatchFilter)> {
+        coordinator
+            .watchers_target
+            .iter()
+            .map(|package| {
+                let is_linked = match coordinator.dependencies_to_projects_map.get(&package.path) {
+                    Some(value) => !value.is_empty(),
+                    None => false,
+                };
 
-                let _ = log_to_file(
-                    &format!("got change events: {}", &current_path),
-                    shared_logger,
-                );
+                let filter = match is_linked {
+                    true => WatchFilter::published(package),
+                    false => WatchFilter::new(package),
+                };
 
-                current_path.to_string()
+                (package.clone(), filter)
             })
-            .filter(|x| *x != "");
+            .collect()
+    }
+
+    fn handle_change_file_events(
+        events: Vec<DebouncedEvent>,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        pending: &mut HashMap<String, PendingChanges>,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        let targets = match coordinator_lock.lock() {
+            Ok(value) => Self::get_watch_filters(&value),
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
 
-        let was_file_changed = match event.kind {
-            EventKind::Create(_) => true,
-            EventKind::Modify(_) => true,
-            EventKind::Remove(_) => true,
-            _ => false,
+                return;
+            }
         };
 
-        if !was_file_changed {
-            return;
+        for event in events.iter() {
+            Self::handle_event(pending, event, shared_logger, &targets)
         }
+    }
 
-        let targets = coordinator.watchers_target.clone();
-
-        for current_event_path in event_paths {
-            for watched_path in targets.iter() {
-                let is_from_project = current_event_path.starts_with(&watched_path.path);
+    /// build the projects whose debounce interval passed, unless their changed files have the
+    /// same content as for the last successful build
+    fn build_due_changes(
+        pending: &mut HashMap<String, PendingChanges>,
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        scheduler: &SchedulerSender,
+        shared_logger: &CoordinatorLogger,
+    ) {
+        let now = Instant::now();
 
-                let output_path = match watched_path.path.ends_with("/") {
-                    true => watched_path.path.clone() + &watched_path.output_name,
-                    false => watched_path.path.clone() + "/" + &watched_path.output_name,
-                };
+        let due_projects = pending
+            .iter()
+            .filter(|(_, changes)| changes.deadline <= now)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<String>>();
 
-                let is_output_folder = current_event_path.starts_with(&output_path);
+        if due_projects.is_empty() {
+            return;
+        }
 
-                if !is_from_project {
-                    continue;
-                }
+        let mut coordinator = match coordinator_lock.lock() {
+            Ok(value) => value,
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
 
-                if is_output_folder {
-                    continue;
-                }
+                return;
+            }
+        };
 
-                paths.push(watched_path.path.clone());
+        let mut changed_paths = Vec::new();
 
-                // TODO: recursion
-                let parents = coordinator
-                    .dependencies_to_projects_map
-                    .get(&watched_path.path);
+        for project_path in due_projects {
+            let changes = match pending.remove(&project_path) {
+                Some(value) => value,
+                None => continue,
+            };
 
-                let parents = match parents {
-                    Some(value) => value.to_vec(),
-                    None => vec![],
-                };
+            let is_changed = coordinator
+                .content_hashes
+                .entry(project_path.clone())
+                .or_default()
+                .update(&changes.paths);
 
-                dbg!(
-                    &parents,
-                    &watched_path.path,
-                    &coordinator.dependencies_to_projects_map
-                );
+            if !is_changed {
+                shared_logger
+                    .with_project(&project_path)
+                    .info("contents unchanged, skipping build");
 
-                for path in parents {
-                    paths.push(path);
-                }
+                continue;
             }
+
+            changed_paths.push(project_path);
+        }
+
+        if changed_paths.is_empty() {
+            return;
         }
+
+        Self::request_builds(&coordinator, changed_paths, scheduler, shared_logger);
     }
 
-    pub fn handle_change_file_events(
-        events: Vec<DebouncedEvent>,
-        coordinator_lock: &Arc<Mutex<Coordinator>>,
-        shared_logger: &LogFile,
+    /// send the changed projects and everything that depends on them to the build scheduler
+    pub fn request_builds(
+        coordinator: &Coordinator,
+        changed_paths: Vec<String>,
+        scheduler: &SchedulerSender,
+        shared_logger: &CoordinatorLogger,
     ) {
-        let mut paths: UniqueVec<String> = UniqueVec::new();
-
-        let coordinator = match coordinator_lock.lock() {
+        let paths = match get_build_order(
+            &changed_paths,
+            &coordinator.dependencies_to_projects_map,
+            &coordinator.projects_to_dependencies_map,
+        ) {
             Ok(value) => value,
             Err(error) => {
-                let _ = log_to_file(
-                    &format!("error getting coordinator: {}", error.to_string()),
-                    shared_logger,
-                );
+                shared_logger.error(&format!("can't order builds: {}", error));
 
                 return;
             }
         };
 
-        for event in events.iter() {
-            Self::handle_event(&mut paths, event, shared_logger, &coordinator)
-        }
+        let dependencies = coordinator.projects_to_dependencies_map.clone();
 
-        let _ = log_to_file(
-            &format!(
-                "target paths: {}",
-                &paths
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<String>>()
-                    .join("\n")
-            ),
-            shared_logger,
-        );
-
-        for path in paths {
-            let _ = log_to_file(&format!("running build for path: {}", &path), shared_logger);
-
-            match run_node_command(&path, "build") {
-                Ok(_) => (),
-                Err(error) => {
-                    let _ =
-                        log_to_file(&format!("error building project: {}", error), shared_logger);
-                }
-            };
+        shared_logger.info(&format!("target paths: {}", paths.join(", ")));
+
+        if let Err(error) = scheduler.send(SchedulerMessage::Build(paths, dependencies)) {
+            shared_logger.error(&format!("error sending builds to scheduler: {}", error));
         }
     }
 
     pub fn handle_file_change(
         reciver: Receiver<Result<Vec<DebouncedEvent>, Vec<Error>>>,
         coordinator_lock: Arc<Mutex<Coordinator>>,
-        kill_thread_reciever: Receiver<String>,
-        shared_logger: LogFile,
+        scheduler: SchedulerSender,
+        shared_logger: CoordinatorLogger,
     ) -> JoinHandle<()> {
-        let handle = thread::spawn(move || loop {
-            let kill_massage = kill_thread_reciever.try_recv();
-
-            if let Ok(message) = kill_massage {
-                let _ = log_to_file(&format!("Terminating. {} ", &message), &shared_logger);
-
-                break;
-            }
-
-            let event_result_msg = reciver.try_recv();
-
-            if event_result_msg.is_err() {
-                continue;
-            }
-
-            let event_result = event_result_msg.unwrap();
+        // the channel is closed when the watcher is stopped, which ends the loop. without pending
+        // changes it blocks until the next event, otherwise until the closest deadline
+        let handle = thread::spawn(move || {
+            let mut pending: HashMap<String, PendingChanges> = HashMap::new();
+
+            loop {
+                let next_deadline = pending.values().map(|changes| changes.deadline).min();
+
+                let event_result = match next_deadline {
+                    Some(deadline) => {
+                        match reciver
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                        {
+                            Ok(value) => Some(value),
+                            Err(RecvTimeoutError::Timeout) => None,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        }
+                    }
+                    None => match reciver.recv() {
+                        Ok(value) => Some(value),
+                        Err(_) => break,
+                    },
+                };
 
-            if event_result.is_err() {
-                let _ = log_to_file("event result error", &shared_logger);
+                match event_result {
+                    Some(Ok(events)) => Self::handle_change_file_events(
+                        events,
+                        &coordinator_lock,
+                        &mut pending,
+                        &shared_logger,
+                    ),
+                    Some(Err(_)) => shared_logger.warn("event result error"),
+                    None => {}
+                }
 
-                continue;
+                Self::build_due_changes(
+                    &mut pending,
+                    &coordinator_lock,
+                    &scheduler,
+                    &shared_logger,
+                );
             }
 
-            let events = event_result.unwrap();
-
-            Self::handle_change_file_events(events, &coordinator_lock, &shared_logger);
+            shared_logger.debug("Terminating updates manager: watcher stopped");
         });
 
         handle
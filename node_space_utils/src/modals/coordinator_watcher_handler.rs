@@ -1,19 +1,18 @@
 use std::{
     path::Path,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
-    },
+    sync::{mpsc::Receiver, Arc, Mutex},
 };
 
 use notify::RecursiveMode;
 
 use crate::{
-    errors::watcher::WatcherError,
-    watch_coordinator::coordinator::log_utils::{log_to_file, LogFile},
+    errors::watcher::WatcherError, watch_coordinator::coordinator::log_utils::CoordinatorLogger,
 };
 
-use super::coordinator::{Coordinator, SharedWatcherClone};
+use super::{
+    coordinator::{Coordinator, CoordinatorWatcher},
+    coordinator_control::ControlMessage,
+};
 
 pub struct CoordinatorWatcherHandler;
 
@@ -22,101 +21,101 @@ impl CoordinatorWatcherHandler {
         Self
     }
 
-    pub fn handle_watcher(
-        coordinator_lock: Arc<Mutex<Coordinator>>,
-        watcher_lock: SharedWatcherClone,
-        term: Arc<AtomicBool>,
-        shared_logger: LogFile,
+    /// add and remove file watchers so they match the watched targets of the coordinator
+    fn sync_watchers(
+        coordinator_lock: &Arc<Mutex<Coordinator>>,
+        current_watcher: &mut CoordinatorWatcher,
+        shared_logger: &CoordinatorLogger,
     ) {
-        let _ = log_to_file("handle_file_change", &shared_logger);
+        let mut coordinator = match coordinator_lock.lock() {
+            Ok(value) => value,
+            Err(error) => {
+                shared_logger.error(&format!("error getting coordinator: {}", error));
+
+                return;
+            }
+        };
+
+        let current_watcher_targets = coordinator.watchers_target.clone();
+
+        for watcher_target in current_watcher_targets.iter() {
+            if !coordinator.active_watchers.contains(&watcher_target.path) {
+                shared_logger
+                    .with_project(&watcher_target.path)
+                    .info("adding watcher");
 
-        loop {
-            if term.load(Ordering::Relaxed) {
-                let _ = log_to_file("got kill", &shared_logger);
+                coordinator
+                    .active_watchers
+                    .push(watcher_target.path.clone());
 
-                break;
+                match current_watcher
+                    .watch(Path::new(&watcher_target.path), RecursiveMode::Recursive)
+                {
+                    Ok(_) => {}
+                    Err(error) => {
+                        shared_logger
+                            .with_project(&watcher_target.path)
+                            .error(&WatcherError::CantCreateWatcher(error.to_string()).to_string());
+                    }
+                };
             }
+        }
 
-            let mut current_watcher = match watcher_lock.lock() {
-                Ok(value) => value,
+        let removed_watchers = coordinator
+            .active_watchers
+            .iter()
+            .filter(|active_watcher| {
+                !coordinator
+                    .watchers_target
+                    .iter()
+                    .any(|p| &p.path == *active_watcher)
+            })
+            .cloned()
+            .collect::<Vec<String>>();
+
+        for active_watcher in removed_watchers.iter() {
+            shared_logger
+                .with_project(active_watcher)
+                .info("removing watcher");
+
+            match current_watcher.unwatch(Path::new(active_watcher)) {
+                Ok(_) => {}
                 Err(error) => {
-                    let _ = log_to_file(
-                        &format!("error getting watcher: {}", error.to_string()),
-                        &shared_logger,
-                    );
-
-                    continue;
+                    shared_logger
+                        .with_project(active_watcher)
+                        .error(&WatcherError::CantCreateWatcher(error.to_string()).to_string());
                 }
             };
+        }
 
-            let mut coordinator = match coordinator_lock.lock() {
-                Ok(value) => value,
-                Err(error) => {
-                    let _ = log_to_file(
-                        &format!("error getting coordinator: {}", error.to_string()),
-                        &shared_logger,
-                    );
+        coordinator
+            .active_watchers
+            .retain(|active_watcher| !removed_watchers.contains(active_watcher));
+    }
 
-                    continue;
-                }
-            };
+    /// runs on the coordinator main thread until a stop message, then stops the watcher which
+    /// closes the file events channel
+    pub fn handle_watcher(
+        coordinator_lock: Arc<Mutex<Coordinator>>,
+        mut current_watcher: CoordinatorWatcher,
+        control_reciever: Receiver<ControlMessage>,
+        shared_logger: CoordinatorLogger,
+    ) {
+        shared_logger.debug("handle_watcher");
 
-            let current_watcher_targets = coordinator.watchers_target.clone();
-
-            for watcher_target in current_watcher_targets.iter() {
-                if !coordinator.active_watchers.contains(&watcher_target.path) {
-                    let _ = log_to_file(
-                        &format!("adding watcher: {}", &watcher_target.path),
-                        &shared_logger,
-                    );
-
-                    coordinator
-                        .active_watchers
-                        .push(watcher_target.path.clone());
-
-                    match current_watcher
-                        .watch(Path::new(&watcher_target.path), RecursiveMode::Recursive)
-                    {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let _ = log_to_file(
-                                &format!("{}", WatcherError::CantCreateWatcher(error.to_string())),
-                                &shared_logger,
-                            );
-                        }
-                    };
+        for message in control_reciever.iter() {
+            match message {
+                ControlMessage::SyncWatchers => {
+                    Self::sync_watchers(&coordinator_lock, &mut current_watcher, &shared_logger)
                 }
-            }
+                ControlMessage::Stop(reason) => {
+                    shared_logger.debug(&format!("got kill: {}", reason));
 
-            let mut new_list = coordinator.active_watchers.clone();
-            let current_list_clone = coordinator.active_watchers.clone();
-
-            for (index, active_watcher) in current_list_clone.iter().enumerate() {
-                if !coordinator
-                    .watchers_target
-                    .iter()
-                    .any(|p| &p.path == active_watcher)
-                {
-                    let _ = log_to_file(
-                        &format!("removing watcher: {}", &active_watcher),
-                        &shared_logger,
-                    );
-
-                    new_list.remove(index);
-
-                    match current_watcher.unwatch(Path::new(active_watcher)) {
-                        Ok(_) => {}
-                        Err(error) => {
-                            let _ = log_to_file(
-                                &format!("{}", WatcherError::CantCreateWatcher(error.to_string())),
-                                &shared_logger,
-                            );
-                        }
-                    };
+                    break;
                 }
             }
-
-            coordinator.active_watchers = new_list;
         }
+
+        current_watcher.stop();
     }
 }
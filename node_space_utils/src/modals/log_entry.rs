@@ -0,0 +1,153 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_utils::{format_timestamp, parse_timestamp};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LogLevel::Error => write!(f, "ERROR"),
+            LogLevel::Warn => write!(f, "WARN"),
+            LogLevel::Info => write!(f, "INFO"),
+            LogLevel::Debug => write!(f, "DEBUG"),
+        }
+    }
+}
+
+/// the json lines shape of a `LogEntry`
+#[derive(Serialize, Deserialize)]
+struct JsonLogEntry {
+    timestamp: String,
+    level: LogLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    message: String,
+}
+
+/// a single coordinator log line:
+/// ```text
+/// 2025-03-01T12:30:05.123Z INFO [/home/user/dev/package1] running build
+/// ```
+/// the project part is optional, in json format the same entry is:
+/// ```json
+/// {"timestamp": "2025-03-01T12:30:05.123Z", "level": "info", "project": "/home/user/dev/package1", "message": "running build"}
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// unix timestamp in milliseconds
+    pub timestamp: u64,
+    pub level: LogLevel,
+    pub project: Option<String>,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn new(timestamp: u64, level: LogLevel, project: Option<String>, message: String) -> Self {
+        LogEntry {
+            timestamp,
+            level,
+            project,
+            message,
+        }
+    }
+
+    pub fn format(&self) -> String {
+        match &self.project {
+            Some(project) => format!(
+                "{} {} [{}] {}",
+                format_timestamp(self.timestamp),
+                self.level,
+                project,
+                self.message
+            ),
+            None => format!(
+                "{} {} {}",
+                format_timestamp(self.timestamp),
+                self.level,
+                self.message
+            ),
+        }
+    }
+
+    pub fn format_json(&self) -> String {
+        let json_entry = JsonLogEntry {
+            timestamp: format_timestamp(self.timestamp),
+            level: self.level,
+            project: self.project.clone(),
+            message: self.message.clone(),
+        };
+
+        match serde_json::to_string(&json_entry) {
+            Ok(value) => value,
+            Err(_) => self.format(),
+        }
+    }
+
+    fn parse_json(line: &str) -> Option<Self> {
+        let json_entry = serde_json::from_str::<JsonLogEntry>(line).ok()?;
+
+        Some(LogEntry::new(
+            parse_timestamp(&json_entry.timestamp)?,
+            json_entry.level,
+            json_entry.project,
+            json_entry.message,
+        ))
+    }
+
+    /// returns `None` for lines that do not start a new entry, like the rest of a multi line
+    /// message
+    pub fn parse(line: &str) -> Option<Self> {
+        if line.starts_with('{') {
+            return Self::parse_json(line);
+        }
+
+        let (timestamp, rest) = line.split_once(' ')?;
+        let timestamp = parse_timestamp(timestamp)?;
+
+        let (level, rest) = match rest.split_once(' ') {
+            Some(value) => value,
+            None => (rest, ""),
+        };
+
+        let level = LogLevel::parse(level)?;
+
+        let project_end = match rest.starts_with('[') {
+            true => rest.find("] "),
+            false => None,
+        };
+
+        let (project, message) = match project_end {
+            Some(index) => (Some(rest[1..index].to_string()), &rest[index + 2..]),
+            None => (None, rest),
+        };
+
+        Some(LogEntry::new(
+            timestamp,
+            level,
+            project,
+            message.to_string(),
+        ))
+    }
+}
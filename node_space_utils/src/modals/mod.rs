@@ -1,11 +1,23 @@
+pub mod build_cache;
+pub mod build_result;
 pub mod config_file;
+pub mod content_hashes;
 pub mod coordinator;
+pub mod coordinator_build_scheduler;
+pub mod coordinator_control;
+pub mod coordinator_event;
 pub mod coordinator_listener;
+pub mod coordinator_message;
 pub mod coordinator_pid_manager;
+pub mod coordinator_state;
+pub mod coordinator_subscribers;
 pub mod coordinator_updates_manager;
 pub mod coordinator_watcher_handler;
 pub mod link_action;
+pub mod log_entry;
 pub mod package;
+pub mod package_backup;
 pub mod server_config;
 pub mod socket_build_data;
+pub mod trigger_event;
 pub mod unique_vec;
@@ -1,5 +1,12 @@
+use std::time::Duration;
+
+use notify::EventKind;
 use serde::{Deserialize, Serialize};
 
+use super::trigger_event::TriggerEvent;
+
+const DEFAULT_DEBOUNCE_MS: u64 = 3000;
+
 fn default_dist() -> String {
     "dist".to_string()
 }
@@ -12,6 +19,27 @@ pub struct Package {
 
     #[serde(default = "default_dist")]
     pub output_name: String,
+
+    /// globs relative to the package root, when set only matching changes trigger a build
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// globs relative to the package root that never trigger a build
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// wait for this many milliseconds without changes before building, defaults to 3 seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debounce_ms: Option<u64>,
+
+    /// build once when the watcher starts, unset keeps the default of the watcher: local
+    /// watchers build, the coordinator waits for the first change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_on_start: Option<bool>,
+
+    /// file event kinds that trigger a build, every kind when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trigger_events: Vec<TriggerEvent>,
 }
 
 impl Package {
@@ -31,6 +59,34 @@ impl Package {
             name,
             alias,
             output_name: effective_output_name,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            debounce_ms: None,
+            build_on_start: None,
+            trigger_events: Vec::new(),
+        }
+    }
+
+    pub fn get_debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS))
+    }
+
+    pub fn is_triggered_by(&self, kind: &EventKind) -> bool {
+        match TriggerEvent::from_event_kind(kind) {
+            Some(value) => self.trigger_events.is_empty() || self.trigger_events.contains(&value),
+            None => false,
+        }
+    }
+
+    /// check if the package is referenced by `name`, either by its package name or its alias
+    pub fn is_named(&self, name: &str) -> bool {
+        if self.name == name {
+            return true;
+        }
+
+        match self.alias {
+            Some(ref value) => value == name,
+            None => false,
         }
     }
 }
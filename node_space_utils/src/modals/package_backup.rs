@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// an installed package that was moved out of a project node_modules before linking
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct PackageBackup {
+    pub project_path: String,
+    pub package_name: String,
+    pub backup_path: String,
+}
+
+impl PackageBackup {
+    pub fn new(project_path: String, package_name: String, backup_path: String) -> Self {
+        PackageBackup {
+            project_path,
+            package_name,
+            backup_path,
+        }
+    }
+}
@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{errors::node_space::NodeSpaceError, server::config::DEFAULT_PORT};
+use crate::{
+    errors::node_space::NodeSpaceError,
+    server::{config::DEFAULT_PORT, proxy::is_upstream_target},
+};
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct ServerConfig {
@@ -10,6 +13,12 @@ pub struct ServerConfig {
     pub name: String,
     pub routes: HashMap<String, String>,
     pub main_route: Option<String>,
+    /// pem files used by `server start --tls`, the self signed localhost certificate is used
+    /// when they are not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cert: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_key: Option<String>,
 }
 
 impl ServerConfig {
@@ -18,12 +27,16 @@ impl ServerConfig {
         name: String,
         routes: HashMap<String, String>,
         main_route: Option<String>,
+        tls_cert: Option<String>,
+        tls_key: Option<String>,
     ) -> Self {
         Self {
             port,
             name,
             routes,
             main_route,
+            tls_cert,
+            tls_key,
         }
     }
 
@@ -43,16 +56,31 @@ impl ServerConfig {
             name: "default".to_string(),
             routes: map,
             main_route: Some("/".to_string()),
+            tls_cert: None,
+            tls_key: None,
         }
     }
 
+    /// output dirs of the routes that are served from the file system
+    pub fn get_output_dirs(&self) -> Vec<String> {
+        self.routes
+            .values()
+            .filter(|target| !is_upstream_target(target))
+            .cloned()
+            .collect()
+    }
+
     pub fn get_main_route_output_dir(&self) -> Result<String, NodeSpaceError> {
         if self.main_route.is_none() {
-            let first_key = match self.routes.keys().next() {
-                Some(value) => value,
+            let first_key = match self
+                .routes
+                .iter()
+                .find(|(_, target)| !is_upstream_target(target))
+            {
+                Some((key, _)) => key,
                 None => {
                     return Err(NodeSpaceError::InvalidRoutesConfig(String::from(
-                        "empty routes keys",
+                        "no route serves an output dir",
                     )))
                 }
             };
@@ -69,6 +97,9 @@ impl ServerConfig {
             return Ok(first_value.to_string());
         }
         match self.routes.get(&self.main_route.clone().unwrap()) {
+            Some(value) if is_upstream_target(value) => Err(NodeSpaceError::InvalidRoutesConfig(
+                String::from("the main_route must serve an output dir, not an upstream url"),
+            )),
             Some(value) => Ok(value.to_string()),
             None => {
                 return Err(NodeSpaceError::InvalidRoutesConfig(String::from(
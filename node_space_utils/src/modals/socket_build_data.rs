@@ -7,6 +7,10 @@ pub struct SocketBuildData {
     pub project: Package,
     pub symlinks: Vec<Package>,
     pub watch_only_links: bool,
+    /// the project a dependency registration is made for, the registration goes away with it.
+    /// `None` for a project registered by its own client
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
 }
 
 impl SocketBuildData {
@@ -15,6 +19,13 @@ impl SocketBuildData {
             symlinks,
             project,
             watch_only_links,
+            owner: None,
         }
     }
+
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = Some(owner.to_string());
+
+        self
+    }
 }
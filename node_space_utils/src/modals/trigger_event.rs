@@ -0,0 +1,40 @@
+use notify::{event::ModifyKind, EventKind};
+use serde::{Deserialize, Serialize};
+
+/// kinds of file events that can trigger a build of a project
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerEvent {
+    Create,
+    /// content changes
+    Modify,
+    Rename,
+    Remove,
+    /// permission and timestamp changes, a `touch` without edits
+    Metadata,
+}
+
+impl TriggerEvent {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "create" => Some(TriggerEvent::Create),
+            "modify" => Some(TriggerEvent::Modify),
+            "rename" => Some(TriggerEvent::Rename),
+            "remove" => Some(TriggerEvent::Remove),
+            "metadata" => Some(TriggerEvent::Metadata),
+            _ => None,
+        }
+    }
+
+    /// `None` for access and other events that never trigger a build
+    pub fn from_event_kind(kind: &EventKind) -> Option<Self> {
+        match kind {
+            EventKind::Create(_) => Some(TriggerEvent::Create),
+            EventKind::Modify(ModifyKind::Name(_)) => Some(TriggerEvent::Rename),
+            EventKind::Modify(ModifyKind::Metadata(_)) => Some(TriggerEvent::Metadata),
+            EventKind::Modify(_) => Some(TriggerEvent::Modify),
+            EventKind::Remove(_) => Some(TriggerEvent::Remove),
+            _ => None,
+        }
+    }
+}
@@ -29,6 +29,13 @@ const PACKAGE_JSON_ORDER: [&str; 13] = [
     "optionalDependencies",
 ];
 
+const DEPENDENCIES_KEYS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
 pub fn is_package_exist(linked_packages: &Vec<Package>, path: &str) -> bool {
     let result = linked_packages.iter().find(|x| &x.path == path);
 
@@ -146,3 +153,23 @@ pub fn get_base_package_data(
 
     Ok((package_json_data, package_name, current_path))
 }
+
+/// get the version range of a dependency as written in the project package json,
+/// looks in every dependencies section
+pub fn get_dependency_version(
+    package_json_data: &Map<String, Value>,
+    package_name: &str,
+) -> Option<String> {
+    for dependencies_name in DEPENDENCIES_KEYS {
+        let version = package_json_data
+            .get(dependencies_name)
+            .and_then(|deps| deps.get(package_name))
+            .and_then(Value::as_str);
+
+        if let Some(value) = version {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
@@ -1,5 +1,6 @@
 use std::env::{current_dir, var};
 use std::fs;
+use std::os::unix::fs::symlink;
 use std::path::{Path, PathBuf};
 
 use crate::errors::invalid_project::InvalidNodeProjectError;
@@ -31,27 +32,100 @@ pub fn expand_tilde(path: &str) -> Result<PathBuf, InvalidNodeProjectError> {
     Ok(full_path)
 }
 
-pub fn get_package_path_from_node_modules(
+/// copy a directory tree, symlinks are recreated instead of followed
+fn copy_dir_all(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = to.join(entry.file_name());
+
+        if file_type.is_symlink() {
+            symlink(fs::read_link(entry.path())?, &target)?;
+        } else if file_type.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// move a directory, falls back to copy and remove when the paths are on different devices
+pub fn move_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if to.exists() {
+        fs::remove_dir_all(to)?;
+    }
+
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_dir_all(from, to)?;
+    fs::remove_dir_all(from)
+}
+
+/// move the installed package out of the project node_modules into `backup_path`,
+/// an existing symlink is removed since there is nothing to restore from it
+/// returns true if a backup was made
+pub fn backup_package_from_node_modules(
     path: &str,
     package_name: &str,
-) -> Result<String, SymlinkError> {
+    backup_path: &Path,
+) -> Result<bool, SymlinkError> {
     let node_modules_package_path = Path::new(path).join("node_modules").join(package_name);
 
-    if node_modules_package_path.exists() {
-        dbg!("removed path");
+    let metadata = match fs::symlink_metadata(&node_modules_package_path) {
+        Ok(value) => value,
+        Err(_) => return Ok(false),
+    };
 
-        match fs::remove_dir_all(&node_modules_package_path) {
-            Ok(_) => {}
-            Err(error) => {
-                return Err(SymlinkError::CantRemoveExistingDir(error.to_string()));
-            }
-        }
+    if metadata.file_type().is_symlink() {
+        return match fs::remove_file(&node_modules_package_path) {
+            Ok(_) => Ok(false),
+            Err(error) => Err(SymlinkError::CantRemoveSymlink(error.to_string())),
+        };
     }
 
-    let value = node_modules_package_path
+    match move_dir(&node_modules_package_path, backup_path) {
+        Ok(_) => Ok(true),
+        Err(error) => Err(SymlinkError::CantBackupPackage(error.to_string())),
+    }
+}
+
+/// move a package backup back into the project node_modules
+/// returns false if the backup is missing
+pub fn restore_package_to_node_modules(
+    path: &str,
+    package_name: &str,
+    backup_path: &Path,
+) -> Result<bool, SymlinkError> {
+    if !backup_path.exists() {
+        return Ok(false);
+    }
+
+    let node_modules_package_path = Path::new(path).join("node_modules").join(package_name);
+
+    match move_dir(backup_path, &node_modules_package_path) {
+        Ok(_) => Ok(true),
+        Err(error) => Err(SymlinkError::CantRestoreBackup(error.to_string())),
+    }
+}
+
+pub fn get_package_path_from_node_modules(
+    path: &str,
+    package_name: &str,
+) -> Result<String, SymlinkError> {
+    let node_modules_package_path = Path::new(path).join("node_modules").join(package_name);
+
+    node_modules_package_path
         .to_str()
         .map(String::from)
-        .ok_or_else(|| SymlinkError::InvalidSymlink);
-
-    value
+        .ok_or(SymlinkError::InvalidSymlink)
 }
@@ -1,21 +1,45 @@
 use crate::{
     args::project_args::ProjectArgs,
     errors::node_space::NodeSpaceError,
-    modals::{config_file::ConfigFile, package::Package},
+    modals::{config_file::ConfigFile, package::Package, trigger_event::TriggerEvent},
     package_utils::get_base_package_data,
 };
 
+fn parse_trigger_events(values: &[String]) -> Result<Vec<TriggerEvent>, NodeSpaceError> {
+    let mut trigger_events = Vec::new();
+
+    for value in values {
+        match TriggerEvent::parse(value) {
+            Some(trigger_event) => trigger_events.push(trigger_event),
+            None => {
+                return Err(NodeSpaceError::InvalidWatchSettings(format!(
+                    "unknown trigger event: {}",
+                    value
+                )))
+            }
+        }
+    }
+
+    Ok(trigger_events)
+}
+
 pub fn add_project(args: &ProjectArgs) -> Result<bool, NodeSpaceError> {
     let mut config_file = ConfigFile::new()?;
 
     let (_, package_name, current_path) = get_base_package_data(None)?;
-    let package = Package::new(
+    let mut package = Package::new(
         current_path,
         package_name,
         args.name.clone(),
         args.output_dir.clone(),
     );
 
+    package.include = args.include.clone();
+    package.exclude = args.exclude.clone();
+    package.debounce_ms = args.debounce;
+    package.build_on_start = args.build_on_start;
+    package.trigger_events = parse_trigger_events(&args.trigger)?;
+
     config_file.add_project(&package)?;
 
     Ok(true)
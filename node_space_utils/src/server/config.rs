@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, fs, path::PathBuf};
 
 use crate::{
     args::server_args::ConfigServerArgs,
@@ -6,14 +6,18 @@ use crate::{
     modals::{config_file::ConfigFile, package::Package, server_config::ServerConfig},
 };
 
+use super::proxy::{is_upstream_target, parse_upstream};
+
 pub const DEFAULT_PORT: &str = "3000";
 
 /// routes should have the format "route_name => project_name, route_name2 => project_name2"
+/// a target can also be an upstream url, "/api => http://localhost:8080"
 /// we than transfrom it to
 /// ```
 /// {
 /// "route_name": project_output_dir  -> default "dist"
 /// "route_name2": project2_output_dir  -> default "dist"
+/// "/api": "http://localhost:8080"
 /// }
 ///
 /// ```
@@ -50,6 +54,22 @@ fn build_routes(
         let route_name = route_defenition_parts[0];
         let project_name = route_defenition_parts[1];
 
+        if is_upstream_target(project_name.trim()) {
+            if parse_upstream(project_name.trim()).is_none() {
+                return Err(NodeSpaceError::InvalidRoutesConfig(format!(
+                    "Bad upstream url, only http://host:port urls are supported: {}",
+                    project_name
+                )));
+            }
+
+            map.insert(
+                route_name.trim().to_string(),
+                project_name.trim().to_string(),
+            );
+
+            continue;
+        }
+
         if !project_map.contains_key(project_name.trim()) {
             return Err(NodeSpaceError::InvalidRoutesConfig(format!(
                 "Unrecognized project name: {}",
@@ -89,6 +109,22 @@ fn build_routes(
     Ok(map)
 }
 
+/// cert and key paths are stored absolute so the server can start from any folder
+fn resolve_tls_path(path: &Option<String>) -> Result<Option<String>, NodeSpaceError> {
+    let path = match path {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    match fs::canonicalize(path) {
+        Ok(value) => Ok(Some(value.to_string_lossy().to_string())),
+        Err(error) => Err(NodeSpaceError::InvalidTlsConfig(format!(
+            "can't find {}: {}",
+            path, error
+        ))),
+    }
+}
+
 pub async fn handle_server_config(args: &ConfigServerArgs) -> Result<bool, NodeSpaceError> {
     let mut config_file = ConfigFile::new()?;
     let project_map = config_file.build_name_project_mapper();
@@ -96,7 +132,17 @@ pub async fn handle_server_config(args: &ConfigServerArgs) -> Result<bool, NodeS
     let port = args.port.clone().map_or(DEFAULT_PORT.to_string(), |v| v);
 
     let routes = build_routes(args.routes.clone(), project_map)?;
-    let server_config = ServerConfig::new(port, args.name.clone(), routes, args.main_route.clone());
+    let tls_cert = resolve_tls_path(&args.tls_cert)?;
+    let tls_key = resolve_tls_path(&args.tls_key)?;
+
+    let server_config = ServerConfig::new(
+        port,
+        args.name.clone(),
+        routes,
+        args.main_route.clone(),
+        tls_cert,
+        tls_key,
+    );
 
     config_file
         .server_config
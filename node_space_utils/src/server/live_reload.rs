@@ -0,0 +1,136 @@
+use std::{convert::Infallible, path::Path, thread, time::Duration};
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::{stream, Stream};
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{DebouncedEvent, Debouncer, RecommendedCache};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{errors::node_space::NodeSpaceError, watcher_utils::create_watcher_instance};
+
+/// server sent events endpoint the injected script listens on
+pub const LIVE_RELOAD_ROUTE: &str = "/__node_space/live-reload";
+
+/// builds write their output in bursts, wait for the last file before reloading
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `EventSource` reconnects by itself when the server restarts, `__OUTPUT_DIRS__` is replaced
+/// with the output dirs the page is served from
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(() => {
+  const outputDirs = __OUTPUT_DIRS__;
+  const source = new EventSource("/__node_space/live-reload");
+  source.addEventListener("reload", (event) => {
+    if (outputDirs.includes(event.data)) window.location.reload();
+  });
+})();
+</script>
+"#;
+
+pub type ReloadSender = broadcast::Sender<String>;
+
+type OutputWatcher = Debouncer<RecommendedWatcher, RecommendedCache>;
+
+/// add the live reload script before `</body>`, or at the end of documents without one, the page
+/// only reloads when one of `output_dirs` changes
+pub fn inject_live_reload_script(html: &str, output_dirs: &[String]) -> String {
+    let output_dirs = serde_json::to_string(output_dirs)
+        .unwrap_or_else(|_| String::from("[]"))
+        .replace('<', "\\u003c");
+
+    let script = LIVE_RELOAD_SCRIPT.replace("__OUTPUT_DIRS__", &output_dirs);
+
+    match html.rfind("</body>") {
+        Some(index) => format!("{}{}{}", &html[..index], script, &html[index..]),
+        None => format!("{}{}", html, script),
+    }
+}
+
+/// builds often delete the output dir first, its parent is watched as well so the watch is added
+/// again once the dir is created
+fn watch_output_dir(watcher: &mut OutputWatcher, output_dir: &Path) {
+    if let Some(parent) = output_dir.parent() {
+        if let Err(error) = watcher.watch(parent, RecursiveMode::NonRecursive) {
+            println!("⚠️  can't watch {}: {}", parent.display(), error);
+        }
+    }
+
+    if output_dir.exists() {
+        if let Err(error) = watcher.watch(output_dir, RecursiveMode::Recursive) {
+            println!("⚠️  can't watch {}: {}", output_dir.display(), error);
+        }
+    }
+}
+
+fn get_changed_output_dir<'a>(
+    events: &[DebouncedEvent],
+    output_dirs: &'a [String],
+) -> Option<&'a str> {
+    events
+        .iter()
+        .filter(|event| !event.kind.is_access())
+        .flat_map(|event| event.paths.iter())
+        .find_map(|path| {
+            output_dirs
+                .iter()
+                .find(|output_dir| path.starts_with(output_dir.as_str()))
+                .map(String::as_str)
+        })
+}
+
+/// watch the output dirs of the routes and send the changed dir to every open page, the watcher
+/// lives on its own thread until the server stops
+pub fn watch_output_dirs(output_dirs: Vec<String>) -> Result<ReloadSender, NodeSpaceError> {
+    let (sender, _) = broadcast::channel(16);
+    let (reciver, mut watcher) = create_watcher_instance(RELOAD_DEBOUNCE)?;
+
+    for output_dir in output_dirs.iter() {
+        watch_output_dir(&mut watcher, Path::new(output_dir));
+    }
+
+    let reload_sender = sender.clone();
+
+    thread::spawn(move || {
+        for event_result in reciver.iter() {
+            let events = match event_result {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+
+            let output_dir = match get_changed_output_dir(&events, &output_dirs) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            // a new output dir is a fresh inode, the old recursive watch is gone
+            watch_output_dir(&mut watcher, Path::new(output_dir));
+
+            println!("🔄 {} changed, reloading pages", output_dir);
+
+            // no open pages is not an error
+            let _ = reload_sender.send(output_dir.to_string());
+        }
+    });
+
+    Ok(sender)
+}
+
+pub async fn handle_live_reload(
+    sender: ReloadSender,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = stream::unfold(sender.subscribe(), |mut reciver| async move {
+        loop {
+            match reciver.recv().await {
+                Ok(output_dir) => {
+                    let event = Event::default().event("reload").data(output_dir);
+
+                    return Some((Ok(event), reciver));
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
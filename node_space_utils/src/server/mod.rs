@@ -1,2 +1,5 @@
 pub mod config;
+pub mod live_reload;
+pub mod proxy;
 pub mod start;
+pub mod tls;
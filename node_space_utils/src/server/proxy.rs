@@ -0,0 +1,172 @@
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderValue, Uri};
+use axum::response::{IntoResponse, Response};
+use hyper::header::{CONNECTION, HOST, UPGRADE};
+use hyper::StatusCode;
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+
+pub type ProxyClient = Client<HttpConnector, Body>;
+
+/// headers that only apply to a single connection and are not forwarded, `connection` and
+/// `upgrade` are kept for websocket upgrades
+const HOP_BY_HOP_HEADERS: [&str; 6] = [
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+];
+
+pub fn create_proxy_client() -> ProxyClient {
+    Client::builder(TokioExecutor::new()).build(HttpConnector::new())
+}
+
+/// route targets starting with `http://` are forwarded to an upstream server, the others are
+/// output dirs
+pub fn is_upstream_target(target: &str) -> bool {
+    target.starts_with("http://") || target.starts_with("https://")
+}
+
+/// `None` when the target is not an http url with a host, https upstreams are not supported
+pub fn parse_upstream(target: &str) -> Option<Uri> {
+    if !target.starts_with("http://") {
+        return None;
+    }
+
+    let uri = target.parse::<Uri>().ok()?;
+
+    uri.authority()?;
+
+    Some(uri)
+}
+
+/// like nginx `proxy_pass`: an upstream without a path gets the request path as it is, an
+/// upstream with a path gets it in place of the route
+/// `/api` -> `http://localhost:8080`: `/api/users` -> `http://localhost:8080/api/users`
+/// `/mf` -> `http://localhost:3001/`: `/mf/main.js` -> `http://localhost:3001/main.js`
+fn build_upstream_uri(route: &str, target: &str, request_uri: &Uri) -> Option<Uri> {
+    let upstream = parse_upstream(target)?;
+    let request_path = request_uri.path();
+
+    let has_upstream_path = target.trim_start_matches("http://").contains('/');
+
+    let path = match has_upstream_path {
+        false => request_path.to_string(),
+        true => {
+            let rest = request_path
+                .strip_prefix(route.trim_end_matches('/'))
+                .unwrap_or(request_path)
+                .trim_start_matches('/');
+
+            format!("{}/{}", upstream.path().trim_end_matches('/'), rest)
+        }
+    };
+
+    let path_and_query = match request_uri.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+
+    Uri::builder()
+        .scheme("http")
+        .authority(upstream.authority()?.clone())
+        .path_and_query(path_and_query)
+        .build()
+        .ok()
+}
+
+fn bad_gateway(upstream: &str, error: impl std::fmt::Display) -> Response {
+    (
+        StatusCode::BAD_GATEWAY,
+        format!("proxy error - upstream: {}, error: {}", upstream, error),
+    )
+        .into_response()
+}
+
+/// once both sides switched protocols, copy bytes between the browser and the upstream until one
+/// of them closes the connection
+fn bridge_upgrade(request: &mut Request, response: &mut hyper::Response<hyper::body::Incoming>) {
+    let client_upgrade = hyper::upgrade::on(request);
+    let upstream_upgrade = hyper::upgrade::on(response);
+
+    tokio::spawn(async move {
+        let (client, upstream) = match tokio::try_join!(client_upgrade, upstream_upgrade) {
+            Ok(value) => value,
+            Err(error) => {
+                println!("⚠️  websocket upgrade failed: {}", error);
+
+                return;
+            }
+        };
+
+        let mut client = TokioIo::new(client);
+        let mut upstream = TokioIo::new(upstream);
+
+        let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+    });
+}
+
+pub async fn proxy_request(
+    client: ProxyClient,
+    route: String,
+    upstream: String,
+    mut request: Request,
+) -> Response {
+    let uri = match build_upstream_uri(&route, &upstream, request.uri()) {
+        Some(value) => value,
+        None => return bad_gateway(&upstream, "invalid upstream url"),
+    };
+
+    let is_upgrade = request.headers().contains_key(UPGRADE);
+
+    let mut upstream_request = hyper::Request::builder()
+        .method(request.method().clone())
+        .uri(uri.clone());
+
+    for (name, value) in request.headers().iter() {
+        let is_hop_by_hop = HOP_BY_HOP_HEADERS.contains(&name.as_str())
+            || (!is_upgrade && (name == CONNECTION || name == UPGRADE));
+
+        if name == HOST || is_hop_by_hop {
+            continue;
+        }
+
+        upstream_request = upstream_request.header(name, value);
+    }
+
+    // dev servers check the host header, the upstream sees itself as the host
+    if let Some(authority) = uri.authority() {
+        if let Ok(value) = HeaderValue::from_str(authority.as_str()) {
+            upstream_request = upstream_request.header(HOST, value);
+        }
+    }
+
+    if let Some(value) = request.headers().get(HOST) {
+        upstream_request = upstream_request.header("x-forwarded-host", value);
+    }
+
+    // the body of an upgrade request is the upgraded connection, it is taken by `bridge_upgrade`
+    let body = match is_upgrade {
+        true => Body::empty(),
+        false => std::mem::take(request.body_mut()),
+    };
+
+    let upstream_request = match upstream_request.body(body) {
+        Ok(value) => value,
+        Err(error) => return bad_gateway(&upstream, error),
+    };
+
+    let mut response = match client.request(upstream_request).await {
+        Ok(value) => value,
+        Err(error) => return bad_gateway(&upstream, error),
+    };
+
+    if is_upgrade && response.status() == StatusCode::SWITCHING_PROTOCOLS {
+        bridge_upgrade(&mut request, &mut response);
+    }
+
+    response.map(Body::new)
+}
@@ -1,7 +1,8 @@
 use axum::body::Body;
+use axum::extract::Request;
 use axum::http::HeaderValue;
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{any, get};
 use axum::Router;
 use axum::{extract::Path as AxumPath, response::Response};
 use http_body_util::StreamBody;
@@ -16,6 +17,12 @@ use crate::{
     package_utils::get_base_package_data,
 };
 
+use super::live_reload::{
+    handle_live_reload, inject_live_reload_script, watch_output_dirs, LIVE_RELOAD_ROUTE,
+};
+use super::proxy::{create_proxy_client, is_upstream_target, proxy_request};
+use super::tls::{create_rustls_config, get_tls_paths};
+
 pub fn get_config_name(args: &StartServerArgs) -> Result<String, NodeSpaceError> {
     if args.name.is_some() {
         return Ok(args.name.clone().unwrap());
@@ -36,7 +43,26 @@ pub fn get_default_config(config: Option<&ServerConfig>) -> Result<ServerConfig,
     Ok(ServerConfig::default(current_path))
 }
 
-async fn build_file_body_stream(path: String) -> Result<(Body, HeaderValue), NodeSpaceError> {
+/// the output dirs a page of the route is served from, `None` when live reload is off
+fn get_reload_dirs(output_dir: &str, main_route: &str, live_reload: bool) -> Option<Vec<String>> {
+    if !live_reload {
+        return None;
+    }
+
+    let mut reload_dirs = vec![output_dir.to_string()];
+
+    if main_route != output_dir {
+        reload_dirs.push(main_route.to_string());
+    }
+
+    Some(reload_dirs)
+}
+
+/// html pages are read whole to add the live reload script, other files are streamed
+async fn build_file_body_stream(
+    path: String,
+    reload_dirs: Option<&[String]>,
+) -> Result<(Body, HeaderValue), NodeSpaceError> {
     let file_result = tokio::fs::File::open(&path).await;
 
     if let Err(error) = file_result {
@@ -57,6 +83,22 @@ async fn build_file_body_stream(path: String) -> Result<(Body, HeaderValue), Nod
         }
     };
 
+    if let (Some(reload_dirs), true) = (reload_dirs, content_type == "text/html") {
+        let html = match tokio::fs::read_to_string(&path).await {
+            Ok(value) => value,
+            Err(error) => {
+                let message = format!("Can't read file: {}, error: {}", &path, error);
+
+                return Err(NodeSpaceError::ServerError(message));
+            }
+        };
+
+        return Ok((
+            Body::from(inject_live_reload_script(&html, reload_dirs)),
+            content_type,
+        ));
+    }
+
     let reader_stream = ReaderStream::new(file);
     let stream_body = StreamBody::new(reader_stream);
 
@@ -65,19 +107,27 @@ async fn build_file_body_stream(path: String) -> Result<(Body, HeaderValue), Nod
 
 /// gets the outdir real path on the file system: /home/user/dev/project1/dist
 /// gets the request path localhost:3000/project1/index.js -> project1/index.js
-async fn serve_files(output_dir: &str, file_path: String, main_route: String) -> Response {
+async fn serve_files(
+    output_dir: &str,
+    file_path: String,
+    main_route: String,
+    live_reload: bool,
+) -> Response {
     let mut path = String::from(output_dir) + "/" + &file_path;
     path = path.replace("//", "/");
 
-    let (body, content_type) = match build_file_body_stream(path.clone()).await {
-        Err(error) => {
-            dbg!(error);
+    let reload_dirs = get_reload_dirs(output_dir, &main_route, live_reload);
 
-            return serve_html(main_route).await;
-        }
+    let (body, content_type) =
+        match build_file_body_stream(path.clone(), reload_dirs.as_deref()).await {
+            Err(error) => {
+                dbg!(error);
 
-        Ok(value) => value,
-    };
+                return serve_html(main_route, reload_dirs).await;
+            }
+
+            Ok(value) => value,
+        };
 
     let response = Response::builder()
         .status(StatusCode::OK)
@@ -96,22 +146,23 @@ async fn serve_files(output_dir: &str, file_path: String, main_route: String) ->
     }
 }
 
-async fn serve_html(main_route: String) -> Response {
+async fn serve_html(main_route: String, reload_dirs: Option<Vec<String>>) -> Response {
     dbg!("in serve_html");
     let mut path = main_route + "/index.html";
     path = path.replace("//", "/");
 
-    let (body, content_type) = match build_file_body_stream(path.clone()).await {
-        Err(error) => {
-            return (
-                axum::http::StatusCode::NOT_FOUND,
-                format!("file not found error - path: {}, error: {}", path, error),
-            )
-                .into_response();
-        }
+    let (body, content_type) =
+        match build_file_body_stream(path.clone(), reload_dirs.as_deref()).await {
+            Err(error) => {
+                return (
+                    axum::http::StatusCode::NOT_FOUND,
+                    format!("file not found error - path: {}, error: {}", path, error),
+                )
+                    .into_response();
+            }
 
-        Ok(value) => value,
-    };
+            Ok(value) => value,
+        };
 
     let response = Response::builder()
         .status(StatusCode::OK)
@@ -153,14 +204,53 @@ pub async fn handle_server_start(args: &StartServerArgs) -> Result<bool, NodeSpa
         None => server_config.port.clone(),
     };
 
+    let live_reload = !args.no_live_reload;
     let mut app = Router::new();
 
+    if live_reload {
+        let reload_sender = watch_output_dirs(server_config.get_output_dirs())?;
+
+        app = app.route(
+            LIVE_RELOAD_ROUTE,
+            get(move || async move { handle_live_reload(reload_sender).await }),
+        );
+    }
+
+    let proxy_client = create_proxy_client();
+
     for (route, output_dir) in routes.iter() {
+        if is_upstream_target(output_dir) {
+            let proxy_route = route.trim_end_matches('/').to_string();
+
+            // `/api`, `/api/` and everything below it
+            let mut proxy_paths = vec![proxy_route.clone() + "/", proxy_route.clone() + "/{*path}"];
+
+            if !proxy_route.is_empty() {
+                proxy_paths.push(proxy_route.clone());
+            }
+
+            for proxy_path in proxy_paths {
+                let client = proxy_client.clone();
+                let upstream = output_dir.clone();
+                let route_copy = proxy_route.clone();
+
+                app = app.route(
+                    &proxy_path,
+                    any(move |request: Request| async move {
+                        proxy_request(client, route_copy, upstream, request).await
+                    }),
+                );
+            }
+
+            continue;
+        }
+
         let output_clone = output_dir.clone();
         let files_route = (route.to_string() + "/{*file_path}").replace("//", "/");
         let base_route = (route.to_string() + "/").replace("//", "/");
         let main_route_main_copy = main_route.clone();
         let main_route_secondary_copy = main_route.clone();
+        let reload_dirs = get_reload_dirs(output_dir, &main_route, live_reload);
 
         dbg!(route, &base_route);
 
@@ -168,12 +258,14 @@ pub async fn handle_server_start(args: &StartServerArgs) -> Result<bool, NodeSpa
             .route(
                 &files_route,
                 get(move |AxumPath(path): AxumPath<String>| async move {
-                    serve_files(&output_clone, path, main_route_main_copy).await
+                    serve_files(&output_clone, path, main_route_main_copy, live_reload).await
                 }),
             )
             .route(
                 &base_route,
-                get(move || async move { serve_html(main_route_secondary_copy).await }),
+                get(
+                    move || async move { serve_html(main_route_secondary_copy, reload_dirs).await },
+                ),
             )
     }
 
@@ -181,6 +273,32 @@ pub async fn handle_server_start(args: &StartServerArgs) -> Result<bool, NodeSpa
 
     dbg!(&host);
 
+    if args.tls {
+        let (cert_path, key_path) = get_tls_paths(&server_config, args.generate_cert)?;
+        let rustls_config = create_rustls_config(cert_path, key_path).await?;
+
+        let address = match host.parse() {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(NodeSpaceError::ServerError(format!(
+                    "bad address: {}, error: {}",
+                    host, error
+                )))
+            }
+        };
+
+        println!("🔒 serving https://localhost:{}", port);
+
+        if let Err(error) = axum_server::bind_rustls(address, rustls_config)
+            .serve(app.into_make_service())
+            .await
+        {
+            return Err(NodeSpaceError::ServerError(error.to_string()));
+        }
+
+        return Ok(true);
+    }
+
     let listener = tokio::net::TcpListener::bind(host).await.unwrap();
     axum::serve(listener, app).await.unwrap();
     Ok(true)
@@ -0,0 +1,138 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    os::unix::fs::OpenOptionsExt,
+    path::PathBuf,
+};
+
+use axum_server::tls_rustls::RustlsConfig;
+use rcgen::{generate_simple_self_signed, CertifiedKey};
+
+use crate::{
+    errors::node_space::NodeSpaceError, modals::server_config::ServerConfig,
+    path_utils::expand_tilde,
+};
+
+const SELF_SIGNED_CERT_PATH_STR: &str = "~/.config/node-space/certs/localhost.pem";
+const SELF_SIGNED_KEY_PATH_STR: &str = "~/.config/node-space/certs/localhost-key.pem";
+
+/// names the self signed certificate is valid for
+const SELF_SIGNED_NAMES: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+
+fn get_self_signed_paths() -> Result<(PathBuf, PathBuf), NodeSpaceError> {
+    let cert_path = match expand_tilde(SELF_SIGNED_CERT_PATH_STR) {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::InvalidTlsConfig(error.to_string())),
+    };
+
+    let key_path = match expand_tilde(SELF_SIGNED_KEY_PATH_STR) {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::InvalidTlsConfig(error.to_string())),
+    };
+
+    Ok((cert_path, key_path))
+}
+
+/// browsers will warn about the certificate until it is trusted, the key is only readable by the
+/// current user
+pub fn generate_self_signed_cert() -> Result<(PathBuf, PathBuf), NodeSpaceError> {
+    let (cert_path, key_path) = get_self_signed_paths()?;
+
+    let names: Vec<String> = SELF_SIGNED_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    let CertifiedKey { cert, key_pair } = match generate_simple_self_signed(names) {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::InvalidTlsConfig(error.to_string())),
+    };
+
+    if let Some(parent_dir) = cert_path.parent() {
+        if let Err(error) = fs::create_dir_all(parent_dir) {
+            return Err(NodeSpaceError::InvalidTlsConfig(error.to_string()));
+        }
+    }
+
+    if let Err(error) = fs::write(&cert_path, cert.pem()) {
+        return Err(NodeSpaceError::InvalidTlsConfig(error.to_string()));
+    }
+
+    // the mode only applies to new files, an older key may have been created with other
+    // permissions
+    if let Err(error) = fs::remove_file(&key_path) {
+        if error.kind() != ErrorKind::NotFound {
+            return Err(NodeSpaceError::InvalidTlsConfig(error.to_string()));
+        }
+    }
+
+    let mut key_file = match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&key_path)
+    {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::InvalidTlsConfig(error.to_string())),
+    };
+
+    if let Err(error) = key_file.write_all(key_pair.serialize_pem().as_bytes()) {
+        return Err(NodeSpaceError::InvalidTlsConfig(error.to_string()));
+    }
+
+    println!(
+        "🔑 generated a self signed certificate: {}",
+        cert_path.display()
+    );
+
+    Ok((cert_path, key_path))
+}
+
+/// cert and key of the server config, or the self signed localhost certificate which is created
+/// when missing or when `regenerate` is set
+pub fn get_tls_paths(
+    server_config: &ServerConfig,
+    regenerate: bool,
+) -> Result<(PathBuf, PathBuf), NodeSpaceError> {
+    match (&server_config.tls_cert, &server_config.tls_key) {
+        (Some(_), Some(_)) if regenerate => {
+            return Err(NodeSpaceError::InvalidTlsConfig(format!(
+                "{} has its own certificate, --generate-cert is for the self signed one",
+                server_config.name
+            )))
+        }
+        (Some(cert), Some(key)) => return Ok((PathBuf::from(cert), PathBuf::from(key))),
+        (None, None) => {}
+        _ => {
+            return Err(NodeSpaceError::InvalidTlsConfig(String::from(
+                "tls_cert and tls_key must be set together",
+            )))
+        }
+    }
+
+    let (cert_path, key_path) = get_self_signed_paths()?;
+
+    if regenerate || !cert_path.exists() || !key_path.exists() {
+        return generate_self_signed_cert();
+    }
+
+    Ok((cert_path, key_path))
+}
+
+pub async fn create_rustls_config(
+    cert_path: PathBuf,
+    key_path: PathBuf,
+) -> Result<RustlsConfig, NodeSpaceError> {
+    // axum-server is built without a default crypto provider, installing twice is not an error
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    match RustlsConfig::from_pem_file(&cert_path, &key_path).await {
+        Ok(value) => Ok(value),
+        Err(error) => Err(NodeSpaceError::InvalidTlsConfig(format!(
+            "can't load {} and {}: {}",
+            cert_path.display(),
+            key_path.display(),
+            error
+        ))),
+    }
+}
@@ -1 +0,0 @@
-pub mod socket_active_utils;
@@ -1,3 +1,6 @@
+use std::{fs, path::Path};
+
+use crate::errors::symlink::SymlinkError;
 use crate::modals::{link_action::LinkAction, package::Package};
 
 pub fn handle_link_candidate(
@@ -61,3 +64,31 @@ pub fn handle_link_candidate(
 
     return LinkAction::LinkSelf;
 }
+
+/// removes `node_modules/<package_name>` from the project only if it is a symlink, a real install
+/// is never touched
+/// returns false when there was no symlink to remove
+pub fn remove_symlink_from_node_modules(
+    path: &str,
+    package_name: &str,
+) -> Result<bool, SymlinkError> {
+    let symlink_path = Path::new(path).join("node_modules").join(package_name);
+
+    let metadata = match fs::symlink_metadata(&symlink_path) {
+        Ok(value) => value,
+        Err(_) => return Ok(false),
+    };
+
+    if !metadata.file_type().is_symlink() {
+        return Ok(false);
+    }
+
+    match fs::remove_file(&symlink_path) {
+        Ok(_) => Ok(true),
+        Err(error) => Err(SymlinkError::CantRemoveSymlink(format!(
+            "{}: {}",
+            symlink_path.to_string_lossy(),
+            error
+        ))),
+    }
+}
@@ -0,0 +1,144 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// seconds since the unix epoch, 0 if the system clock is before it
+pub fn get_unix_timestamp() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(value) => value.as_secs(),
+        Err(_) => 0,
+    }
+}
+
+/// format a number of seconds as a short human readable duration: `1h 2m 3s`
+pub fn format_duration(total_seconds: u64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        return format!("{}h {}m {}s", hours, minutes, seconds);
+    }
+
+    if minutes > 0 {
+        return format!("{}m {}s", minutes, seconds);
+    }
+
+    format!("{}s", seconds)
+}
+
+/// milliseconds since the unix epoch, 0 if the system clock is before it
+pub fn get_unix_timestamp_millis() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(value) => value.as_millis() as u64,
+        Err(_) => 0,
+    }
+}
+
+/// days since the unix epoch -> (year, month, day)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// (year, month, day) -> days since the unix epoch
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let doy = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+/// format unix milliseconds as an utc rfc 3339 timestamp: `2025-03-01T12:30:05.123Z`
+pub fn format_timestamp(timestamp_millis: u64) -> String {
+    let total_seconds = (timestamp_millis / 1000) as i64;
+    let (year, month, day) = civil_from_days(total_seconds.div_euclid(86400));
+    let seconds_of_day = total_seconds.rem_euclid(86400);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60,
+        seconds_of_day % 60,
+        timestamp_millis % 1000
+    )
+}
+
+/// parse an utc timestamp to unix milliseconds, accepts `2025-03-01`, `2025-03-01T12:30:05`,
+/// `2025-03-01 12:30:05` and the full format written by `format_timestamp`
+pub fn parse_timestamp(value: &str) -> Option<u64> {
+    let value = value.trim().trim_end_matches('Z');
+
+    let (date, time) = match value.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.split('-');
+    let year = date_parts.next()?.parse::<i64>().ok()?;
+    let month = date_parts.next()?.parse::<u32>().ok()?;
+    let day = date_parts.next()?.parse::<u32>().ok()?;
+
+    if date_parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let (seconds_of_day, millis) = match time {
+        Some(time) => {
+            let (time, fraction) = match time.split_once('.') {
+                Some((time, fraction)) => (time, fraction),
+                None => (time, "0"),
+            };
+
+            let mut time_parts = time.split(':');
+            let hours = time_parts.next()?.parse::<i64>().ok()?;
+            let minutes = time_parts.next()?.parse::<i64>().ok()?;
+            let seconds = match time_parts.next() {
+                Some(value) => value.parse::<i64>().ok()?,
+                None => 0,
+            };
+
+            let millis = format!("{:0<3}", fraction)[..3].parse::<i64>().ok()?;
+
+            (hours * 3600 + minutes * 60 + seconds, millis)
+        }
+        None => (0, 0),
+    };
+
+    let total_millis = (days_from_civil(year, month, day) * 86400 + seconds_of_day) * 1000 + millis;
+
+    u64::try_from(total_millis).ok()
+}
+
+/// parse a duration like `30s`, `15m`, `2h` or `1d` to milliseconds
+pub fn parse_duration_millis(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let unit_index = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(unit_index);
+    let amount = amount.parse::<u64>().ok()?;
+
+    let unit_millis = match unit {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        _ => return None,
+    };
+
+    amount.checked_mul(unit_millis)
+}
@@ -0,0 +1,180 @@
+use crate::{
+    args::unlink_args::UnlinkArgs,
+    command_line::node_build::run_npm_install,
+    errors::{config_file::ConfigFileError, node_space::NodeSpaceError, symlink::SymlinkError},
+    modals::{config_file::ConfigFile, package::Package},
+    package_utils::{get_base_package_data, get_dependency_version},
+    path_utils::restore_package_to_node_modules,
+    symlink_utils::remove_symlink_from_node_modules,
+};
+
+use std::{fs, path::Path};
+
+/// install the registry version of the package using the version range from the project
+/// package json, packages that are not declared as a dependency are only removed
+fn reinstall_package(project: &Package, package_name: &str) -> Result<(), NodeSpaceError> {
+    let (package_json_data, _, _) = get_base_package_data(Some(&project.path))?;
+
+    let version = match get_dependency_version(&package_json_data, package_name) {
+        Some(value) => value,
+        None => {
+            println!(
+                "⚠️  {} is not a dependency of {}, skipping install",
+                package_name, project.name
+            );
+
+            return Ok(());
+        }
+    };
+
+    let package_spec = format!("{}@{}", package_name, version);
+
+    run_npm_install(&project.path, &package_spec)?;
+
+    Ok(())
+}
+
+/// put back the install that was replaced by the link, prefers the backup taken when linking
+/// and falls back to the registry version
+fn restore_package(
+    config_file: &mut ConfigFile,
+    project: &Package,
+    package_name: &str,
+    reinstall: bool,
+) -> Result<(), NodeSpaceError> {
+    let backup = config_file.find_backup(&project.path, package_name);
+
+    let backup = match backup {
+        Some(value) => value,
+        None => return reinstall_package(project, package_name),
+    };
+
+    let backup_path = Path::new(&backup.backup_path);
+
+    if reinstall {
+        reinstall_package(project, package_name)?;
+
+        if backup_path.exists() {
+            if let Err(error) = fs::remove_dir_all(backup_path) {
+                println!("⚠️  can't remove backup {}: {}", backup.backup_path, error);
+            }
+        }
+    } else if !restore_package_to_node_modules(&project.path, package_name, backup_path)? {
+        println!("⚠️  backup {} is missing", backup.backup_path);
+
+        reinstall_package(project, package_name)?;
+    }
+
+    config_file.remove_backup(&project.path, package_name)?;
+
+    Ok(())
+}
+
+fn unlink_packages(
+    config_file: &mut ConfigFile,
+    project: &Package,
+    packages: &[Package],
+    reinstall: bool,
+) -> Result<(), NodeSpaceError> {
+    for package in packages.iter() {
+        remove_symlink_from_node_modules(&project.path, &package.name)?;
+        restore_package(config_file, project, &package.name, reinstall)?;
+
+        config_file.remove_symlink(&project.name, package)?;
+
+        println!("🔗 unlinked {} from {}", package.name, project.name);
+    }
+
+    Ok(())
+}
+
+fn unlink_current_package(
+    config_file: &mut ConfigFile,
+    reinstall: bool,
+) -> Result<bool, NodeSpaceError> {
+    let (_, _, current_path) = get_base_package_data(None)?;
+
+    let linked_package = config_file
+        .linked_packages
+        .iter()
+        .find(|package| package.path == current_path)
+        .cloned();
+
+    let linked_package = match linked_package {
+        Some(value) => value,
+        None => {
+            return Err(NodeSpaceError::ConfigFileError(
+                ConfigFileError::MissingLinkedPackage,
+            ))
+        }
+    };
+
+    let project_map = config_file.build_name_project_mapper();
+    let project_names = config_file
+        .symlinks
+        .iter()
+        .filter(|(_, packages)| packages.iter().any(|p| p.path == linked_package.path))
+        .map(|(project_name, _)| project_name.clone())
+        .collect::<Vec<String>>();
+
+    for project_name in project_names {
+        let project = match project_map.get(&project_name) {
+            Some(value) => value.clone(),
+            None => return Err(NodeSpaceError::MissingProject),
+        };
+
+        let packages = config_file
+            .find_project_symlinks(&project_name, None)
+            .into_iter()
+            .filter(|package| package.path == linked_package.path)
+            .collect::<Vec<Package>>();
+
+        unlink_packages(config_file, &project, &packages, reinstall)?;
+    }
+
+    config_file.remove_linked_package(&linked_package.path)?;
+
+    Ok(true)
+}
+
+pub fn handle_unlink_command(unlink_args: &UnlinkArgs) -> Result<bool, NodeSpaceError> {
+    let mut config_file = ConfigFile::new()?;
+
+    if unlink_args.package {
+        return unlink_current_package(&mut config_file, unlink_args.reinstall);
+    }
+
+    let link_name = unlink_args.name.as_deref();
+
+    if unlink_args.group.is_none() {
+        let (_, package_name, current_path) = get_base_package_data(None)?;
+        let project = Package::new(current_path, package_name, None, None);
+
+        let packages = config_file.find_project_symlinks(&project.name, link_name);
+
+        if let (true, Some(name)) = (packages.is_empty(), link_name) {
+            return Err(NodeSpaceError::SymlinkError(SymlinkError::MissingSymlink(
+                name.to_string(),
+            )));
+        }
+
+        unlink_packages(&mut config_file, &project, &packages, unlink_args.reinstall)?;
+
+        return Ok(true);
+    }
+
+    let group_name = unlink_args.group.clone().unwrap();
+
+    let projects = match config_file.groups.get(&group_name) {
+        Some(value) => value.clone(),
+        None => return Err(NodeSpaceError::GroupNameIsNotValid),
+    };
+
+    for project in projects.iter() {
+        let packages = config_file.find_project_symlinks(&project.name, link_name);
+
+        unlink_packages(&mut config_file, project, &packages, unlink_args.reinstall)?;
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,83 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    os::unix::net::UnixStream,
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    errors::{node_space::NodeSpaceError, socket::SocketError},
+    modals::{
+        coordinator_event::{BuildOutputStream, CoordinatorEvent},
+        coordinator_message::{CoordinatorRequest, CoordinatorResponse},
+    },
+};
+
+use super::coordinator_request::{
+    check_response, connect_to_coordinator, read_response, write_request,
+};
+
+fn display_event(event: &CoordinatorEvent, name: &str) {
+    match event {
+        CoordinatorEvent::BuildStarted { .. } => println!("🔨 building {}", name),
+        CoordinatorEvent::BuildOutput { stream, line, .. } => match stream {
+            BuildOutputStream::Stdout => println!("   {} │ {}", name, line),
+            BuildOutputStream::Stderr => eprintln!("   {} │ {}", name, line),
+        },
+        CoordinatorEvent::BuildFinished { result, .. } => match result.success {
+            true => println!("✅ {} built in {}ms", name, result.duration_ms),
+            false => println!("❌ {} failed after {}ms", name, result.duration_ms),
+        },
+        CoordinatorEvent::BuildCancelled { .. } => println!("🚫 {} build cancelled", name),
+        CoordinatorEvent::BuildSkipped { .. } => {
+            println!("⏭️  {} skipped, a dependency failed", name)
+        }
+    }
+}
+
+/// subscribe to the coordinator events and print the builds of `packages` (path -> name) until
+/// the coordinator closes the stream
+pub fn print_build_events(
+    packages: HashMap<String, String>,
+) -> Result<JoinHandle<()>, NodeSpaceError> {
+    let stream = connect_to_coordinator()?;
+
+    write_request(&stream, CoordinatorRequest::Subscribe)?;
+
+    let mut reader = BufReader::new(stream);
+
+    match check_response(read_response(&mut reader)?)? {
+        CoordinatorResponse::Subscribed => {}
+        value => {
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                format!("expected subscribed, got: {:?}", value),
+            )))
+        }
+    };
+
+    let handle = thread::spawn(move || follow_events(reader, packages));
+
+    Ok(handle)
+}
+
+fn follow_events(mut reader: BufReader<UnixStream>, packages: HashMap<String, String>) {
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        };
+
+        let event = match serde_json::from_str::<CoordinatorEvent>(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        if let Some(name) = packages.get(event.get_path()) {
+            display_event(&event, name);
+        }
+    }
+}
@@ -1 +0,0 @@
-pub const COORDINATOR_SOCKET_PATH: &str = "/tmp/node-space-coordinator.sock";
@@ -1,25 +1,60 @@
-use serde_json::to_string;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
 
 use crate::{
     errors::{node_space::NodeSpaceError, socket::SocketError},
-    modals::socket_build_data::SocketBuildData,
+    modals::coordinator_message::{
+        CoordinatorRequest, CoordinatorRequestMessage, CoordinatorResponse,
+    },
 };
 
-use super::constants::COORDINATOR_SOCKET_PATH;
+use crate::watch_coordinator::runtime_dir::get_coordinator_socket_path;
 
-pub fn send_coordinator_request(socket_data: SocketBuildData) -> Result<bool, NodeSpaceError> {
-    let mut stream = match UnixStream::connect(COORDINATOR_SOCKET_PATH) {
-        Ok(value) => value,
+const PING_TIMEOUT: Option<Duration> = Some(Duration::from_millis(500));
+
+pub fn connect_to_coordinator() -> Result<UnixStream, NodeSpaceError> {
+    match UnixStream::connect(get_coordinator_socket_path()) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(NodeSpaceError::SocketError(
+            SocketError::ErrorConnectingToSocket(error.to_string()),
+        )),
+    }
+}
+
+pub fn read_response<R: BufRead>(reader: &mut R) -> Result<CoordinatorResponse, NodeSpaceError> {
+    let mut response_str = String::new();
+
+    match reader.read_line(&mut response_str) {
+        Ok(0) => {
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                "coordinator closed the connection".to_string(),
+            )))
+        }
+        Ok(_) => {}
         Err(error) => {
-            return Err(NodeSpaceError::SocketError(
-                SocketError::ErrorConnectingToSocket(error.to_string()),
-            ))
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                error.to_string(),
+            )))
         }
     };
 
-    let socket_data_string = match to_string(&socket_data) {
+    match serde_json::from_str(&response_str) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+            error.to_string(),
+        ))),
+    }
+}
+
+pub fn write_request(
+    mut stream: &UnixStream,
+    request: CoordinatorRequest,
+) -> Result<(), NodeSpaceError> {
+    let message = CoordinatorRequestMessage::new(request);
+
+    let message_str = match serde_json::to_string(&message) {
         Ok(value) => value,
         Err(error) => {
             return Err(NodeSpaceError::SocketError(
@@ -28,12 +63,55 @@ pub fn send_coordinator_request(socket_data: SocketBuildData) -> Result<bool, No
         }
     };
 
-    match writeln!(stream, "{}", socket_data_string) {
-        Ok(_) => Ok(true),
-        Err(error) => {
-            return Err(NodeSpaceError::SocketError(
-                SocketError::ErrorSendingDataInSocket(error.to_string()),
-            ))
-        }
+    match writeln!(stream, "{}", message_str) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(NodeSpaceError::SocketError(
+            SocketError::ErrorSendingDataInSocket(error.to_string()),
+        )),
+    }
+}
+
+pub fn send_request_on_stream(
+    stream: &UnixStream,
+    request: CoordinatorRequest,
+) -> Result<CoordinatorResponse, NodeSpaceError> {
+    write_request(stream, request)?;
+
+    let mut reader = BufReader::new(stream);
+
+    read_response(&mut reader)
+}
+
+pub fn send_coordinator_request(
+    request: CoordinatorRequest,
+) -> Result<CoordinatorResponse, NodeSpaceError> {
+    let stream = connect_to_coordinator()?;
+
+    send_request_on_stream(&stream, request)
+}
+
+/// health check of the coordinator listening on `socket_path`, returns its pid when it answers in
+/// time
+pub fn ping_coordinator(socket_path: &Path) -> Option<u32> {
+    let stream = UnixStream::connect(socket_path).ok()?;
+
+    stream.set_read_timeout(PING_TIMEOUT).ok()?;
+    stream.set_write_timeout(PING_TIMEOUT).ok()?;
+
+    match send_request_on_stream(&stream, CoordinatorRequest::Ping) {
+        Ok(CoordinatorResponse::Pong { pid }) => Some(pid),
+        _ => None,
+    }
+}
+
+/// turn an error response into a `NodeSpaceError`, any other response is returned as is
+pub fn check_response(
+    response: CoordinatorResponse,
+) -> Result<CoordinatorResponse, NodeSpaceError> {
+    match response {
+        CoordinatorResponse::Error(error) => Err(NodeSpaceError::SocketError(
+            SocketError::RequestRejected(error.to_string()),
+        )),
+        value => Ok(value),
     }
 }
@@ -0,0 +1,123 @@
+use crate::{
+    args::coordinator_args::CoordinatorStatusArgs,
+    errors::{node_space::NodeSpaceError, socket::SocketError},
+    modals::coordinator_message::{CoordinatorRequest, CoordinatorResponse, CoordinatorStatus},
+    time_utils::{format_duration, get_unix_timestamp},
+};
+
+use super::coordinator_request::{check_response, connect_to_coordinator, send_request_on_stream};
+
+fn display_status(status: &CoordinatorStatus) {
+    println!("🟢 coordinator is running");
+    println!(" ├── instance: {}", status.instance);
+    println!(" ├── pid: {}", status.pid);
+    println!(" ├── uptime: {}", format_duration(status.uptime_seconds));
+    println!();
+
+    println!("👀 watched paths");
+
+    for package in status.watchers_target.iter() {
+        let state = match status.active_watchers.contains(&package.path) {
+            true => "watching",
+            false => "pending",
+        };
+
+        println!(
+            " ├── 📁  name: {}, at ({}) [{}]",
+            package.name, package.path, state
+        );
+    }
+
+    println!();
+
+    let mut projects = status
+        .projects_to_dependencies_map
+        .keys()
+        .collect::<Vec<&String>>();
+
+    projects.sort();
+
+    for project in projects {
+        println!("📦 {}", project);
+
+        for dependency in status.projects_to_dependencies_map[project].iter() {
+            println!(" ├── 📁  {}", dependency);
+        }
+
+        println!();
+    }
+
+    println!("🔨 last builds");
+
+    let mut builds = status.last_builds.iter().collect::<Vec<_>>();
+
+    builds.sort_by_key(|(path, _)| *path);
+
+    let now = get_unix_timestamp();
+
+    for (path, result) in builds {
+        let icon = match result.success {
+            true => "✅",
+            false => "❌",
+        };
+
+        println!(
+            " ├── {} {}, took {}ms, {} ago",
+            icon,
+            path,
+            result.duration_ms,
+            format_duration(now.saturating_sub(result.finished_at))
+        );
+    }
+}
+
+/// returns `None` when there is no coordinator listening on the socket
+pub fn get_coordinator_status() -> Result<Option<CoordinatorStatus>, NodeSpaceError> {
+    let stream = match connect_to_coordinator() {
+        Ok(value) => value,
+        Err(_) => return Ok(None),
+    };
+
+    let response = send_request_on_stream(&stream, CoordinatorRequest::Status)?;
+
+    let status = match check_response(response)? {
+        CoordinatorResponse::Status(value) => *value,
+        value => {
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                format!("expected status, got: {:?}", value),
+            )))
+        }
+    };
+
+    Ok(Some(status))
+}
+
+pub fn handle_coordinator_status(
+    status_args: &CoordinatorStatusArgs,
+) -> Result<bool, NodeSpaceError> {
+    let status = match get_coordinator_status()? {
+        Some(value) => value,
+        None => {
+            println!("🛑 coordinator is not running");
+
+            return Ok(false);
+        }
+    };
+
+    if !status_args.json {
+        display_status(&status);
+
+        return Ok(true);
+    }
+
+    match serde_json::to_string_pretty(&status) {
+        Ok(value) => println!("{}", value),
+        Err(error) => {
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                error.to_string(),
+            )))
+        }
+    };
+
+    Ok(true)
+}
@@ -0,0 +1,108 @@
+use std::fs;
+
+use serde::Serialize;
+
+use crate::{
+    args::coordinator_args::CoordinatorListArgs,
+    command_line::process::is_process_running,
+    errors::{node_space::NodeSpaceError, socket::SocketError},
+    watch_coordinator::runtime_dir::{
+        get_instance_dir_of, get_instance_name, get_runtime_dir, list_instance_names,
+        PID_FILE_NAME, SOCKET_FILE_NAME,
+    },
+};
+
+use super::coordinator_request::ping_coordinator;
+
+#[derive(Serialize, Debug, Clone)]
+struct CoordinatorInstance {
+    name: String,
+    pid: Option<u32>,
+    running: bool,
+    /// the instance the current directory resolves to
+    current: bool,
+}
+
+fn get_instance(name: &str, current_instance: &str) -> Result<CoordinatorInstance, NodeSpaceError> {
+    let instance_dir = get_instance_dir_of(name);
+
+    let pid = match fs::read_to_string(instance_dir.join(PID_FILE_NAME)) {
+        Ok(value) => value.trim().parse::<u32>().ok(),
+        Err(_) => None,
+    };
+
+    let running = match pid {
+        Some(value) => {
+            is_process_running(value)?
+                && ping_coordinator(&instance_dir.join(SOCKET_FILE_NAME)).is_some()
+        }
+        None => false,
+    };
+
+    Ok(CoordinatorInstance {
+        name: name.to_string(),
+        pid,
+        running,
+        current: name == current_instance,
+    })
+}
+
+fn display_instances(instances: &[CoordinatorInstance]) {
+    if instances.is_empty() {
+        println!(
+            "🛑 no coordinator instances in {}",
+            get_runtime_dir().display()
+        );
+
+        return;
+    }
+
+    for instance in instances {
+        let icon = match instance.running {
+            true => "🟢",
+            false => "⚪",
+        };
+
+        let state = match (instance.running, instance.pid) {
+            (true, Some(pid)) => format!("pid: {}", pid),
+            _ => "stopped".to_string(),
+        };
+
+        let current = match instance.current {
+            true => " (current)",
+            false => "",
+        };
+
+        println!("{} {}{} [{}]", icon, instance.name, current, state);
+    }
+}
+
+pub fn handle_coordinator_list(list_args: &CoordinatorListArgs) -> Result<bool, NodeSpaceError> {
+    let current_instance = get_instance_name();
+    let mut instances = Vec::new();
+
+    for name in list_instance_names() {
+        let instance = get_instance(&name, &current_instance)?;
+
+        if instance.running || list_args.all {
+            instances.push(instance);
+        }
+    }
+
+    if !list_args.json {
+        display_instances(&instances);
+
+        return Ok(!instances.is_empty());
+    }
+
+    match serde_json::to_string_pretty(&instances) {
+        Ok(value) => println!("{}", value),
+        Err(error) => {
+            return Err(NodeSpaceError::SocketError(SocketError::InvalidResponse(
+                error.to_string(),
+            )))
+        }
+    };
+
+    Ok(!instances.is_empty())
+}
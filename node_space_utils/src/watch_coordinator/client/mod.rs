@@ -1,3 +1,8 @@
-pub mod constants;
+pub mod build_events;
 pub mod coordinator_request;
+pub mod handle_coordinator_status;
+pub mod list_coordinators;
+pub mod restart_coordinator;
 pub mod start_coordinator;
+pub mod stop_coordinator;
+pub mod unwatch_project;
@@ -0,0 +1,53 @@
+use crate::{
+    args::coordinator_args::CoordinatorRestartArgs,
+    errors::node_space::NodeSpaceError,
+    modals::{coordinator_message::CoordinatorRequest, socket_build_data::SocketBuildData},
+};
+
+use super::{
+    coordinator_request::{check_response, send_coordinator_request},
+    handle_coordinator_status::get_coordinator_status,
+    start_coordinator::start_coordinator,
+    stop_coordinator::stop_coordinator,
+};
+
+fn restore_registrations(registrations: Vec<SocketBuildData>) -> Result<(), NodeSpaceError> {
+    for data in registrations {
+        let project_name = data.project.name.clone();
+
+        check_response(send_coordinator_request(CoordinatorRequest::Register(
+            Box::new(data),
+        ))?)?;
+
+        println!("👀 restored {}", project_name);
+    }
+
+    Ok(())
+}
+
+/// stop the running coordinator and start a new one with the same registered projects
+pub fn handle_coordinator_restart(
+    restart_args: &CoordinatorRestartArgs,
+) -> Result<bool, NodeSpaceError> {
+    let status = get_coordinator_status()?;
+
+    let (registrations, jobs) = match status {
+        Some(value) => (
+            value.get_registrations(),
+            restart_args.jobs.or(Some(value.max_jobs)),
+        ),
+        None => (Vec::new(), restart_args.jobs),
+    };
+
+    if stop_coordinator(false)? {
+        println!("🛑 coordinator stopped");
+    }
+
+    start_coordinator(jobs)?;
+
+    restore_registrations(registrations)?;
+
+    println!("🟢 coordinator restarted");
+
+    Ok(true)
+}
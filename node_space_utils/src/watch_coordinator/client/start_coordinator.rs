@@ -3,51 +3,109 @@ use libc::{close, exit, fork, setsid, STDERR_FILENO, STDIN_FILENO, STDOUT_FILENO
 use std::{
     os::unix::process::CommandExt,
     process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
     command_line::process::is_process_running,
     errors::{node_space::NodeSpaceError, watcher::WatcherError},
     modals::{coordinator_pid_manager::CoordinatorPIDManager, socket_build_data::SocketBuildData},
-    socket::socket_active_utils::is_socket_active,
     watch_coordinator::coordinator_communication::send_data_to_coordinator,
     watcher_utils::add_local_watcher,
 };
 
-use crate::watch_coordinator::client::constants::COORDINATOR_SOCKET_PATH;
+use crate::watch_coordinator::{
+    coordinator::{lock_file::CoordinatorLock, log_utils::CoordinatorLogger},
+    runtime_dir::{ensure_runtime_dir, get_coordinator_socket_path},
+};
 
-pub fn is_coordinator_running() -> Result<bool, NodeSpaceError> {
-    let pid_file_manager = CoordinatorPIDManager::new();
+use super::{coordinator_request::ping_coordinator, stop_coordinator::cleanup_coordinator_files};
 
-    if !pid_file_manager.is_exists() {
-        return Ok(false);
-    }
+const START_TIMEOUT: Duration = Duration::from_secs(10);
 
-    let pid = pid_file_manager.read_pid()?;
+/// a coordinator is running when its process is alive and it answers a ping, the pid and socket
+/// files of a coordinator that is gone are removed
+/// the files are only removed while holding the coordinator lock, a coordinator that is starting
+/// holds it and gets waited for instead
+pub fn is_coordinator_running() -> Result<bool, NodeSpaceError> {
+    let pid_manager = CoordinatorPIDManager::new();
+    let logger = CoordinatorLogger::client();
+
+    let pid = match pid_manager.is_exists() {
+        true => pid_manager.read_pid().ok(),
+        false => None,
+    };
 
-    let process_active = is_process_running(pid)?;
+    let process_active = match pid {
+        Some(value) => is_process_running(value)?,
+        None => false,
+    };
 
-    dbg!(process_active);
+    logger.debug(&format!(
+        "coordinator process {:?} running: {}",
+        pid, process_active
+    ));
 
     if !process_active {
+        if !pid_manager.is_exists() && !get_coordinator_socket_path().exists() {
+            return Ok(false);
+        }
+
+        let _lock = match CoordinatorLock::try_acquire()? {
+            Some(value) => value,
+            None => {
+                logger.debug("coordinator lock is taken, waiting for the coordinator");
+
+                return Ok(wait_for_coordinator(START_TIMEOUT));
+            }
+        };
+
+        logger.info("removing stale coordinator pid and socket files");
+
+        cleanup_coordinator_files(&pid_manager)?;
+
         return Ok(false);
     }
 
-    let socket_active = is_socket_active(COORDINATOR_SOCKET_PATH)?;
+    let is_answering = ping_coordinator(&get_coordinator_socket_path()).is_some();
 
-    dbg!(socket_active);
+    logger.debug(&format!("coordinator answered ping: {}", is_answering));
 
-    Ok(socket_active)
+    Ok(is_answering)
 }
 
-pub fn start_coordinator() -> Result<(), NodeSpaceError> {
+/// wait until the coordinator answers a ping on its socket
+pub fn wait_for_coordinator(timeout: Duration) -> bool {
+    let started_at = Instant::now();
+    let socket_path = get_coordinator_socket_path();
+
+    while started_at.elapsed() < timeout {
+        if ping_coordinator(&socket_path).is_some() {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    false
+}
+
+pub fn start_coordinator(jobs: Option<usize>) -> Result<(), NodeSpaceError> {
+    let logger = CoordinatorLogger::client();
+
     if is_coordinator_running()? {
-        dbg!("coordinator is running skiping init...");
+        logger.debug("coordinator is running skiping init...");
 
         return Ok(());
     }
 
-    dbg!("spawning node-space");
+    let runtime_dir = ensure_runtime_dir()?;
+
+    logger.debug(&format!(
+        "spawning node-space with runtime dir {}",
+        runtime_dir.display()
+    ));
 
     unsafe {
         let pid = fork();
@@ -59,7 +117,16 @@ pub fn start_coordinator() -> Result<(), NodeSpaceError> {
         }
 
         if pid > 0 {
-            dbg!("parent continue");
+            logger.debug("parent continue");
+
+            // reap the first child, it exits right after forking the coordinator
+            libc::waitpid(pid, std::ptr::null_mut(), 0);
+
+            if !wait_for_coordinator(START_TIMEOUT) {
+                return Err(NodeSpaceError::CantStartCoordinator(
+                    "coordinator did not answer on its socket".to_string(),
+                ));
+            }
 
             return Ok(());
         }
@@ -86,17 +153,24 @@ pub fn start_coordinator() -> Result<(), NodeSpaceError> {
         close(STDOUT_FILENO);
         close(STDERR_FILENO);
 
-        Command::new("node-space")
-            .arg("coordinator")
-            .arg("start")
+        let mut command = Command::new("node-space");
+
+        command.arg("coordinator").arg("start");
+
+        if let Some(value) = jobs {
+            command.arg("--jobs").arg(value.to_string());
+        }
+
+        let error = command
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .stdin(Stdio::null())
             .exec();
 
-        return Err(NodeSpaceError::CantStartCoordinator(
-            "Exec command failed".to_string(),
-        ));
+        Err(NodeSpaceError::CantStartCoordinator(format!(
+            "Exec command failed: {}",
+            error
+        )))
     }
 }
 
@@ -110,7 +184,10 @@ pub fn add_local_project_watcher(data: SocketBuildData) -> Result<(), NodeSpaceE
 }
 
 pub fn request_build_watcher_for_project(data: SocketBuildData) -> Result<bool, NodeSpaceError> {
-    dbg!("request_build_watcher_for_project");
+    CoordinatorLogger::client().debug(&format!(
+        "request_build_watcher_for_project: {}",
+        &data.project.path
+    ));
 
     send_data_to_coordinator(data)?;
 
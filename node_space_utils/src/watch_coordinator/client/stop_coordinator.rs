@@ -0,0 +1,131 @@
+use std::{
+    fs, thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    args::coordinator_args::CoordinatorStopArgs,
+    command_line::process::{is_process_running, terminate_process},
+    errors::node_space::NodeSpaceError,
+    modals::{
+        coordinator_message::{CoordinatorRequest, CoordinatorResponse},
+        coordinator_pid_manager::CoordinatorPIDManager,
+    },
+    watch_coordinator::runtime_dir::get_coordinator_socket_path,
+};
+
+use super::coordinator_request::{connect_to_coordinator, send_request_on_stream};
+
+const STOP_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// ask the coordinator to stop through the socket, returns false when it did not accept
+fn request_stop() -> bool {
+    let stream = match connect_to_coordinator() {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+
+    if stream.set_read_timeout(Some(STOP_REQUEST_TIMEOUT)).is_err() {
+        return false;
+    }
+
+    matches!(
+        send_request_on_stream(&stream, CoordinatorRequest::Stop),
+        Ok(CoordinatorResponse::Accepted)
+    )
+}
+
+fn wait_for_exit(pid: u32, timeout: Duration) -> Result<bool, NodeSpaceError> {
+    let started_at = Instant::now();
+
+    while started_at.elapsed() < timeout {
+        if !is_process_running(pid)? {
+            return Ok(true);
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(!is_process_running(pid)?)
+}
+
+/// remove the socket and pid files left behind by a coordinator that did not exit cleanly
+pub fn cleanup_coordinator_files(
+    pid_manager: &CoordinatorPIDManager,
+) -> Result<(), NodeSpaceError> {
+    pid_manager.delete_pid()?;
+
+    let socket_path = get_coordinator_socket_path();
+
+    if !socket_path.exists() {
+        return Ok(());
+    }
+
+    match fs::remove_file(socket_path) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(NodeSpaceError::CantStopCoordinator(format!(
+            "can't remove socket file: {}",
+            error
+        ))),
+    }
+}
+
+/// stop the coordinator, first by asking it through the socket and then with SIGTERM
+/// returns false when no coordinator was running
+pub fn stop_coordinator(force: bool) -> Result<bool, NodeSpaceError> {
+    let pid_manager = CoordinatorPIDManager::new();
+
+    let pid = match pid_manager.is_exists() {
+        true => pid_manager.read_pid().ok(),
+        false => None,
+    };
+
+    let is_requested = !force && request_stop();
+
+    let pid = match pid {
+        Some(value) => value,
+        None => {
+            cleanup_coordinator_files(&pid_manager)?;
+
+            return Ok(is_requested);
+        }
+    };
+
+    if is_requested && wait_for_exit(pid, STOP_TIMEOUT)? {
+        cleanup_coordinator_files(&pid_manager)?;
+
+        return Ok(true);
+    }
+
+    if !is_process_running(pid)? {
+        cleanup_coordinator_files(&pid_manager)?;
+
+        return Ok(is_requested);
+    }
+
+    terminate_process(pid)?;
+
+    if !wait_for_exit(pid, STOP_TIMEOUT)? {
+        return Err(NodeSpaceError::CantStopCoordinator(format!(
+            "process {} is still running after SIGTERM",
+            pid
+        )));
+    }
+
+    cleanup_coordinator_files(&pid_manager)?;
+
+    Ok(true)
+}
+
+pub fn handle_coordinator_stop(stop_args: &CoordinatorStopArgs) -> Result<bool, NodeSpaceError> {
+    if !stop_coordinator(stop_args.force)? {
+        println!("🛑 coordinator is not running");
+
+        return Ok(false);
+    }
+
+    println!("🛑 coordinator stopped");
+
+    Ok(true)
+}
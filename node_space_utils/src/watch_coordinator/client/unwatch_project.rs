@@ -0,0 +1,48 @@
+use std::fs;
+
+use crate::{
+    args::coordinator_args::CoordinatorUnwatchArgs,
+    errors::{invalid_project::InvalidNodeProjectError, node_space::NodeSpaceError},
+    modals::coordinator_message::CoordinatorRequest,
+    path_utils::get_current_path,
+};
+
+use super::coordinator_request::{check_response, connect_to_coordinator, send_request_on_stream};
+
+fn get_project_path(unwatch_args: &CoordinatorUnwatchArgs) -> Result<String, NodeSpaceError> {
+    let path = match &unwatch_args.path {
+        Some(value) => value,
+        None => return Ok(get_current_path()?),
+    };
+
+    match fs::canonicalize(path) {
+        Ok(value) => Ok(value.to_string_lossy().to_string()),
+        Err(error) => Err(NodeSpaceError::InvalidNodeProjectError(
+            InvalidNodeProjectError::Other(format!("{}: {}", path, error)),
+        )),
+    }
+}
+
+pub fn handle_coordinator_unwatch(
+    unwatch_args: &CoordinatorUnwatchArgs,
+) -> Result<bool, NodeSpaceError> {
+    let path = get_project_path(unwatch_args)?;
+
+    let stream = match connect_to_coordinator() {
+        Ok(value) => value,
+        Err(_) => {
+            println!("🛑 coordinator is not running");
+
+            return Ok(false);
+        }
+    };
+
+    let response =
+        send_request_on_stream(&stream, CoordinatorRequest::Unwatch { path: path.clone() })?;
+
+    check_response(response)?;
+
+    println!("🙈 stopped watching {}", path);
+
+    Ok(true)
+}
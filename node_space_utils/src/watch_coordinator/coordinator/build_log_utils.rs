@@ -0,0 +1,66 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    errors::node_space::NodeSpaceError,
+    watch_coordinator::runtime_dir::{get_build_logs_dir, hash_path},
+};
+
+pub type BuildLogFile = Arc<Mutex<File>>;
+
+/// `/home/user/dev/package1` -> `<runtime dir>/builds/home_user_dev_package1-1a2b3c4d.log`, the
+/// hash keeps paths that only differ in replaced characters apart
+pub fn get_build_log_path(project_path: &str) -> PathBuf {
+    let file_name = project_path
+        .trim_matches('/')
+        .chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect::<String>();
+
+    get_build_logs_dir().join(format!(
+        "{}-{:08x}.log",
+        file_name,
+        hash_path(Path::new(project_path))
+    ))
+}
+
+/// create the build log of the project, truncating the output of the previous build
+pub fn create_build_log_file(project_path: &str) -> Result<BuildLogFile, NodeSpaceError> {
+    if let Err(error) = fs::create_dir_all(get_build_logs_dir()) {
+        return Err(NodeSpaceError::CantCreateLogFile(error.to_string()));
+    }
+
+    let file = match OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .open(get_build_log_path(project_path))
+    {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::CantCreateLogFile(error.to_string())),
+    };
+
+    Ok(Arc::new(Mutex::new(file)))
+}
+
+/// write a line of build output as is, without the coordinator log formatting
+pub fn write_build_log_line(line: &str, build_log: &BuildLogFile) -> Result<(), NodeSpaceError> {
+    let mut file = match build_log.lock() {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::CantWriteLogFile(error.to_string())),
+    };
+
+    match writeln!(file, "{}", line) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(NodeSpaceError::CantWriteLogFile(error.to_string())),
+    }
+}
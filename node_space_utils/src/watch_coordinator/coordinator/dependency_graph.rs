@@ -0,0 +1,120 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::errors::build::BuildError;
+
+#[derive(PartialEq)]
+enum VisitState {
+    Visiting,
+    Done,
+}
+
+/// collect the changed packages and every project that depends on them, directly or through
+/// other packages, in discovery order
+fn collect_dependents(
+    changed_paths: &[String],
+    dependencies_to_projects_map: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut affected: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for path in changed_paths {
+        if seen.insert(path.clone()) {
+            affected.push(path.clone());
+        }
+    }
+
+    let mut index = 0;
+
+    while index < affected.len() {
+        let parents = dependencies_to_projects_map.get(&affected[index]);
+
+        if let Some(parents) = parents {
+            for parent in parents {
+                if seen.insert(parent.clone()) {
+                    affected.push(parent.clone());
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    affected
+}
+
+fn visit(
+    path: &str,
+    affected: &HashSet<String>,
+    projects_to_dependencies_map: &HashMap<String, Vec<String>>,
+    states: &mut HashMap<String, VisitState>,
+    stack: &mut Vec<String>,
+    order: &mut Vec<String>,
+) -> Result<(), BuildError> {
+    match states.get(path) {
+        Some(VisitState::Done) => return Ok(()),
+        Some(VisitState::Visiting) => {
+            let start = stack.iter().position(|x| x == path).unwrap_or(0);
+            let mut cycle = stack[start..].to_vec();
+
+            cycle.push(path.to_string());
+
+            return Err(BuildError::DependencyCycle(cycle.join(" -> ")));
+        }
+        None => {}
+    }
+
+    states.insert(path.to_string(), VisitState::Visiting);
+    stack.push(path.to_string());
+
+    if let Some(dependencies) = projects_to_dependencies_map.get(path) {
+        for dependency in dependencies {
+            if !affected.contains(dependency) {
+                continue;
+            }
+
+            visit(
+                dependency,
+                affected,
+                projects_to_dependencies_map,
+                states,
+                stack,
+                order,
+            )?;
+        }
+    }
+
+    stack.pop();
+    states.insert(path.to_string(), VisitState::Done);
+    order.push(path.to_string());
+
+    Ok(())
+}
+
+/// get every project that has to be rebuilt after `changed_paths` changed, ordered so that
+/// dependencies are built before the projects that use them
+/// returns `BuildError::DependencyCycle` with the cycle path when the graph is not a DAG
+pub fn get_build_order(
+    changed_paths: &[String],
+    dependencies_to_projects_map: &HashMap<String, Vec<String>>,
+    projects_to_dependencies_map: &HashMap<String, Vec<String>>,
+) -> Result<Vec<String>, BuildError> {
+    let affected_list = collect_dependents(changed_paths, dependencies_to_projects_map);
+    let affected: HashSet<String> = affected_list.iter().cloned().collect();
+
+    let mut states: HashMap<String, VisitState> = HashMap::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut order: Vec<String> = Vec::new();
+
+    for path in affected_list.iter() {
+        visit(
+            path,
+            &affected,
+            projects_to_dependencies_map,
+            &mut states,
+            &mut stack,
+            &mut order,
+        )?;
+    }
+
+    Ok(order)
+}
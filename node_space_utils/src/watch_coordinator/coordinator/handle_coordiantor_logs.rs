@@ -1,40 +1,270 @@
 use std::{
-    io::Error,
-    process::{Child, Command, Stdio},
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    os::unix::fs::MetadataExt,
+    path::PathBuf,
+    thread,
+    time::Duration,
 };
 
 use crate::{
-    args::coordinator_args::CoordinatorLogArgs, command_line::node_build::handle_command_result,
+    args::coordinator_args::CoordinatorLogArgs,
     errors::node_space::NodeSpaceError,
+    modals::log_entry::{LogEntry, LogLevel},
+    time_utils::{get_unix_timestamp_millis, parse_duration_millis, parse_timestamp},
 };
 
-use super::log_utils::COORDINATOR_LOG_FILE;
+use crate::watch_coordinator::runtime_dir::{
+    get_coordinator_log_path, get_coordinator_previous_log_path,
+};
+
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+struct LogFilter {
+    project: Option<String>,
+    level: Option<LogLevel>,
+    /// unix timestamp in milliseconds
+    since: Option<u64>,
+}
 
-fn create_log_command(log_args: &CoordinatorLogArgs) -> Result<Child, Error> {
-    if log_args.watch {
-        let spwn = Command::new("cat")
-            .arg(COORDINATOR_LOG_FILE)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .spawn();
+impl LogFilter {
+    fn new(log_args: &CoordinatorLogArgs) -> Result<Self, NodeSpaceError> {
+        let level = match &log_args.level {
+            Some(value) => match LogLevel::parse(value) {
+                Some(level) => Some(level),
+                None => {
+                    return Err(NodeSpaceError::InvalidLogFilter(format!(
+                        "unknown level: {}",
+                        value
+                    )))
+                }
+            },
+            None => None,
+        };
 
-        return spwn;
+        let since = match &log_args.since {
+            Some(value) => match (parse_duration_millis(value), parse_timestamp(value)) {
+                (Some(duration), _) => Some(get_unix_timestamp_millis().saturating_sub(duration)),
+                (None, Some(timestamp)) => Some(timestamp),
+                (None, None) => {
+                    return Err(NodeSpaceError::InvalidLogFilter(format!(
+                        "since should be a duration like 15m or a timestamp, got: {}",
+                        value
+                    )))
+                }
+            },
+            None => None,
+        };
+
+        Ok(LogFilter {
+            project: log_args.project.clone(),
+            level,
+            since,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.project.is_none() && self.level.is_none() && self.since.is_none()
     }
 
-    let spwn = Command::new("cat")
-        .arg(COORDINATOR_LOG_FILE)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn();
+    /// entries without a project field match when the project appears in the message
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(level) = self.level {
+            if entry.level > level {
+                return false;
+            }
+        }
+
+        if let Some(since) = self.since {
+            if entry.timestamp < since {
+                return false;
+            }
+        }
 
-    spwn
+        let project = match &self.project {
+            Some(value) => value,
+            None => return true,
+        };
+
+        match &entry.project {
+            Some(entry_project) => entry_project.contains(project.as_str()),
+            None => entry.message.contains(project.as_str()),
+        }
+    }
+}
+
+/// reads the coordinator log from where it stopped last time, the file is reopened when it is
+/// replaced (rotation) and read from the start when it gets shorter (truncation)
+struct LogFollower {
+    path: PathBuf,
+    filter: LogFilter,
+    file: Option<File>,
+    inode: u64,
+    offset: u64,
+    partial_line: Vec<u8>,
+    /// lines that are not an entry of their own belong to the last entry
+    is_entry_matching: bool,
+}
+
+impl LogFollower {
+    fn new(path: PathBuf, filter: LogFilter) -> Self {
+        let is_entry_matching = filter.is_empty();
+
+        LogFollower {
+            path,
+            filter,
+            file: None,
+            inode: 0,
+            offset: 0,
+            partial_line: Vec::new(),
+            is_entry_matching,
+        }
+    }
+
+    fn open(&mut self) -> Result<bool, NodeSpaceError> {
+        let file = match File::open(&self.path) {
+            Ok(value) => value,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(NodeSpaceError::CantReadLogFile(error.to_string())),
+        };
+
+        self.inode = match file.metadata() {
+            Ok(value) => value.ino(),
+            Err(error) => return Err(NodeSpaceError::CantReadLogFile(error.to_string())),
+        };
+
+        self.file = Some(file);
+        self.offset = 0;
+        self.partial_line.clear();
+
+        Ok(true)
+    }
+
+    fn print_line(&mut self, line: &str) {
+        if let Some(entry) = LogEntry::parse(line) {
+            self.is_entry_matching = self.filter.matches(&entry);
+        }
+
+        if self.is_entry_matching {
+            println!("{}", line);
+        }
+    }
+
+    fn read_available(&mut self) -> Result<(), NodeSpaceError> {
+        let file = match self.file.as_mut() {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        let mut buffer = Vec::new();
+
+        let size = match file.read_to_end(&mut buffer) {
+            Ok(value) => value,
+            Err(error) => return Err(NodeSpaceError::CantReadLogFile(error.to_string())),
+        };
+
+        self.offset += size as u64;
+        self.partial_line.extend_from_slice(&buffer);
+
+        let last_line_end = match self.partial_line.iter().rposition(|byte| *byte == b'\n') {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        let rest = self.partial_line.split_off(last_line_end + 1);
+        let complete = std::mem::replace(&mut self.partial_line, rest);
+
+        for line in String::from_utf8_lossy(&complete).lines() {
+            self.print_line(line);
+        }
+
+        Ok(())
+    }
+
+    fn flush_partial_line(&mut self) {
+        if self.partial_line.is_empty() {
+            return;
+        }
+
+        let line = String::from_utf8_lossy(&self.partial_line).to_string();
+
+        self.partial_line.clear();
+        self.print_line(&line);
+    }
+
+    /// reopen the log when it was rotated or removed, rewind it when it was truncated
+    fn check_file(&mut self) -> Result<(), NodeSpaceError> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(value) => value,
+            Err(_) => {
+                if self.file.is_some() {
+                    self.read_available()?;
+                    self.flush_partial_line();
+                    self.file = None;
+                }
+
+                return Ok(());
+            }
+        };
+
+        if self.file.is_none() || metadata.ino() != self.inode {
+            self.read_available()?;
+            self.flush_partial_line();
+            self.open()?;
+
+            return Ok(());
+        }
+
+        if metadata.len() >= self.offset {
+            return Ok(());
+        }
+
+        if let Some(file) = self.file.as_mut() {
+            if let Err(error) = file.seek(SeekFrom::Start(0)) {
+                return Err(NodeSpaceError::CantReadLogFile(error.to_string()));
+            }
+        }
+
+        self.offset = 0;
+        self.partial_line.clear();
+
+        Ok(())
+    }
+
+    fn follow(&mut self) -> Result<(), NodeSpaceError> {
+        loop {
+            self.check_file()?;
+            self.read_available()?;
+
+            thread::sleep(FOLLOW_INTERVAL);
+        }
+    }
 }
 
 pub fn handle_coordinator_logs(log_args: &CoordinatorLogArgs) -> Result<bool, NodeSpaceError> {
-    let spwn = create_log_command(log_args);
+    let filter = LogFilter::new(log_args)?;
+    let path = match log_args.previous {
+        true => get_coordinator_previous_log_path(),
+        false => get_coordinator_log_path(),
+    };
+
+    let mut follower = LogFollower::new(path.clone(), filter);
 
-    match handle_command_result(spwn) {
-        Ok(value) => Ok(value),
-        Err(error) => Err(error.into()),
+    if !follower.open()? && !log_args.watch {
+        println!("🛑 coordinator log not found at {}", path.display());
+
+        return Ok(false);
     }
+
+    follower.read_available()?;
+
+    if !log_args.watch {
+        follower.flush_partial_line();
+
+        return Ok(true);
+    }
+
+    follower.follow()?;
+
+    Ok(true)
 }
@@ -1,10 +1,22 @@
-use std::sync::{atomic::AtomicBool, Arc};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
 
-use signal_hook::consts::TERM_SIGNALS;
+use signal_hook::{consts::TERM_SIGNALS, iterator::Signals};
 
-use crate::errors::node_space::NodeSpaceError;
+use crate::{
+    errors::node_space::NodeSpaceError,
+    modals::coordinator_control::{ControlMessage, ControlSender},
+};
 
-pub fn handle_termination_signals() -> Result<Arc<AtomicBool>, NodeSpaceError> {
+/// the first termination signal asks the coordinator to stop, a second one exits right away
+pub fn handle_termination_signals(
+    control: ControlSender,
+) -> Result<Arc<AtomicBool>, NodeSpaceError> {
     let term = Arc::new(AtomicBool::new(false));
 
     for sig in TERM_SIGNALS {
@@ -12,12 +24,22 @@ pub fn handle_termination_signals() -> Result<Arc<AtomicBool>, NodeSpaceError> {
             Ok(_) => {}
             Err(error) => return Err(NodeSpaceError::CantPlaceSigTermHandler(error.to_string())),
         };
-
-        match signal_hook::flag::register(*sig, Arc::clone(&term)) {
-            Ok(_) => {}
-            Err(error) => return Err(NodeSpaceError::CantPlaceSigTermHandler(error.to_string())),
-        };
     }
 
+    let mut signals = match Signals::new(TERM_SIGNALS) {
+        Ok(value) => value,
+        Err(error) => return Err(NodeSpaceError::CantPlaceSigTermHandler(error.to_string())),
+    };
+
+    let signal_term = Arc::clone(&term);
+
+    thread::spawn(move || {
+        if let Some(signal) = signals.forever().next() {
+            signal_term.store(true, Ordering::Relaxed);
+
+            let _ = control.send(ControlMessage::Stop(format!("got signal {}", signal)));
+        }
+    });
+
     Ok(term)
 }
@@ -1,12 +1,72 @@
 use crate::{
-    args::coordinator_args::CoordinatorStartArgs, errors::node_space::NodeSpaceError,
-    modals::coordinator::Coordinator,
+    args::coordinator_args::CoordinatorStartArgs,
+    errors::node_space::NodeSpaceError,
+    modals::{
+        coordinator::Coordinator, coordinator_build_scheduler::get_default_max_jobs,
+        log_entry::LogLevel,
+    },
 };
 
-pub fn handle_start_coordinator(
-    _start_args: &CoordinatorStartArgs,
-) -> Result<bool, NodeSpaceError> {
-    let coordinator = Coordinator::new();
+use super::log_utils::{parse_size, LogFormat, LogSettings};
+use crate::watch_coordinator::runtime_dir::ensure_runtime_dir;
+
+fn get_log_settings(start_args: &CoordinatorStartArgs) -> Result<LogSettings, NodeSpaceError> {
+    let mut settings = LogSettings::from_env(LogLevel::Info);
+
+    if let Some(value) = &start_args.log_level {
+        settings.level = match LogLevel::parse(value) {
+            Some(level) => level,
+            None => {
+                return Err(NodeSpaceError::InvalidLogSettings(format!(
+                    "unknown level: {}",
+                    value
+                )))
+            }
+        };
+    }
+
+    if let Some(value) = &start_args.log_format {
+        settings.format = match LogFormat::parse(value) {
+            Some(format) => format,
+            None => {
+                return Err(NodeSpaceError::InvalidLogSettings(format!(
+                    "unknown format: {}",
+                    value
+                )))
+            }
+        };
+    }
+
+    if let Some(value) = &start_args.log_max_size {
+        settings.max_size = match parse_size(value) {
+            Some(size) if size > 0 => size,
+            _ => {
+                return Err(NodeSpaceError::InvalidLogSettings(format!(
+                    "invalid max size: {}",
+                    value
+                )))
+            }
+        };
+    }
+
+    if let Some(value) = start_args.log_max_files {
+        settings.max_files = value;
+    }
+
+    Ok(settings)
+}
+
+pub fn handle_start_coordinator(start_args: &CoordinatorStartArgs) -> Result<bool, NodeSpaceError> {
+    let max_jobs = match start_args.jobs {
+        Some(value) => value,
+        None => get_default_max_jobs(),
+    };
+
+    let log_settings = get_log_settings(start_args)?;
+
+    ensure_runtime_dir()?;
+
+    let coordinator = Coordinator::new(max_jobs, log_settings, start_args.fresh);
 
     coordinator.start()
 }
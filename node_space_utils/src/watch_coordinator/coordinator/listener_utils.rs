@@ -1,14 +1,14 @@
-use std::os::unix::net::UnixListener;
+use std::os::unix::net::{UnixListener, UnixStream};
 
 use crate::{
     errors::{node_space::NodeSpaceError, socket::SocketError},
-    watch_coordinator::client::constants::COORDINATOR_SOCKET_PATH,
+    watch_coordinator::runtime_dir::get_coordinator_socket_path,
 };
 
-use super::log_utils::{log_to_file, LogFile};
+use super::log_utils::CoordinatorLogger;
 
-pub fn init_listener(shared_logger: &LogFile) -> Result<UnixListener, NodeSpaceError> {
-    let listener = match UnixListener::bind(COORDINATOR_SOCKET_PATH) {
+pub fn init_listener(shared_logger: &CoordinatorLogger) -> Result<UnixListener, NodeSpaceError> {
+    let listener = match UnixListener::bind(get_coordinator_socket_path()) {
         Ok(value) => value,
         Err(error) => {
             return Err(NodeSpaceError::SocketError(
@@ -17,16 +17,14 @@ pub fn init_listener(shared_logger: &LogFile) -> Result<UnixListener, NodeSpaceE
         }
     };
 
-    match listener.set_nonblocking(true) {
-        Ok(_) => {}
-        Err(error) => {
-            return Err(NodeSpaceError::SocketError(
-                SocketError::ErrorConnectingToSocket(error.to_string()),
-            ))
-        }
-    }
-
-    log_to_file("created listener", shared_logger)?;
+    shared_logger.debug("created listener");
 
     Ok(listener)
 }
+
+/// the listener blocks on accept, connecting to it lets it see the kill message
+pub fn wake_listener(shared_logger: &CoordinatorLogger) {
+    if let Err(error) = UnixStream::connect(get_coordinator_socket_path()) {
+        shared_logger.warn(&format!("can't wake listener: {}", error));
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    fs::{File, OpenOptions},
+    os::unix::io::AsRawFd,
+};
+
+use crate::{
+    errors::node_space::NodeSpaceError, watch_coordinator::runtime_dir::get_coordinator_lock_path,
+};
+
+/// held by the coordinator for as long as it runs, a second coordinator of the same instance fails
+/// to take it and exits instead of racing the first one for the socket and pid files
+/// the lock is released by the kernel when the process exits, so it is never stale
+pub struct CoordinatorLock {
+    _file: File,
+}
+
+impl CoordinatorLock {
+    pub fn acquire() -> Result<Self, NodeSpaceError> {
+        match Self::try_acquire()? {
+            Some(value) => Ok(value),
+            None => Err(NodeSpaceError::CoordinatorAlreadyRunning(
+                get_coordinator_lock_path().display().to_string(),
+            )),
+        }
+    }
+
+    /// `None` when a coordinator of the instance is running or starting and holds the lock
+    pub fn try_acquire() -> Result<Option<Self>, NodeSpaceError> {
+        let lock_path = get_coordinator_lock_path();
+
+        let file = match OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+        {
+            Ok(value) => value,
+            Err(error) => return Err(NodeSpaceError::CantStartCoordinator(error.to_string())),
+        };
+
+        let result = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+
+        if result != 0 {
+            let error = std::io::Error::last_os_error();
+
+            return match error.raw_os_error() {
+                Some(libc::EWOULDBLOCK) => Ok(None),
+                _ => Err(NodeSpaceError::CantStartCoordinator(error.to_string())),
+            };
+        }
+
+        Ok(Some(CoordinatorLock { _file: file }))
+    }
+}
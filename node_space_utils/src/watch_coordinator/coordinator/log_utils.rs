@@ -1,80 +1,300 @@
 use std::{
-    fs::OpenOptions,
+    env,
+    fs::{self, File, OpenOptions},
     io::Write,
-    path::Path,
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
-use crate::errors::node_space::NodeSpaceError;
+use crate::{
+    errors::node_space::NodeSpaceError,
+    modals::log_entry::{LogEntry, LogLevel},
+    time_utils::get_unix_timestamp_millis,
+    watch_coordinator::runtime_dir::{get_coordinator_log_path, get_coordinator_previous_log_path},
+};
 
-pub const COORDINATOR_LOG_FILE: &str = "/tmp/node-space-coordinator.log";
+const DEFAULT_LOG_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_LOG_MAX_FILES: usize = 5;
 
-pub type LogFile = Arc<Mutex<std::fs::File>>;
+/// error, warn, info or debug
+pub const LOG_LEVEL_ENV: &str = "NODE_SPACE_LOG_LEVEL";
+/// text or json
+pub const LOG_FORMAT_ENV: &str = "NODE_SPACE_LOG_FORMAT";
+/// size in bytes that triggers a rotation, accepts `K`, `M` and `G` suffixes
+pub const LOG_MAX_SIZE_ENV: &str = "NODE_SPACE_LOG_MAX_SIZE";
+/// number of rotated files to keep next to the current log
+pub const LOG_MAX_FILES_ENV: &str = "NODE_SPACE_LOG_MAX_FILES";
 
-pub fn delete_logging_file() {
-    if !Path::new(&COORDINATOR_LOG_FILE).exists() {
-        return;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    Text,
+    /// one json object per line
+    Json,
+}
+
+impl LogFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Some(LogFormat::Text),
+            "json" => Some(LogFormat::Json),
+            _ => None,
+        }
     }
+}
+
+/// parse a size like `512K`, `10M` or `1G` to bytes
+pub fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim().to_ascii_uppercase();
+    let value = value.trim_end_matches('B');
+
+    let (amount, multiplier) = match value.chars().last()? {
+        'K' => (&value[..value.len() - 1], 1024),
+        'M' => (&value[..value.len() - 1], 1024 * 1024),
+        'G' => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+
+    amount.trim().parse::<u64>().ok()?.checked_mul(multiplier)
+}
 
-    let removed_result = std::fs::remove_file(&COORDINATOR_LOG_FILE);
+#[derive(Debug, Clone, Copy)]
+pub struct LogSettings {
+    pub level: LogLevel,
+    pub format: LogFormat,
+    pub max_size: u64,
+    pub max_files: usize,
+}
+
+impl LogSettings {
+    /// read the settings from the `NODE_SPACE_LOG_*` variables, unknown values and a zero max size
+    /// fall back to the defaults
+    pub fn from_env(default_level: LogLevel) -> Self {
+        let level = env::var(LOG_LEVEL_ENV)
+            .ok()
+            .and_then(|value| LogLevel::parse(&value))
+            .unwrap_or(default_level);
+
+        let format = env::var(LOG_FORMAT_ENV)
+            .ok()
+            .and_then(|value| LogFormat::parse(&value))
+            .unwrap_or(LogFormat::Text);
 
-    if removed_result.is_err() {
-        eprintln!("Error removing logging file");
+        let max_size = env::var(LOG_MAX_SIZE_ENV)
+            .ok()
+            .and_then(|value| parse_size(&value))
+            .filter(|size| *size > 0)
+            .unwrap_or(DEFAULT_LOG_MAX_SIZE);
+
+        let max_files = env::var(LOG_MAX_FILES_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_LOG_MAX_FILES);
+
+        LogSettings {
+            level,
+            format,
+            max_size,
+            max_files,
+        }
     }
 }
 
-pub fn create_logging_file() -> Result<LogFile, NodeSpaceError> {
-    delete_logging_file();
+/// `coordinator.log.1` is the newest rotated file
+pub fn get_rotated_log_path(index: usize) -> PathBuf {
+    let mut path = get_coordinator_log_path().into_os_string();
 
-    let file = match OpenOptions::new()
+    path.push(format!(".{}", index));
+
+    PathBuf::from(path)
+}
+
+fn open_log_file() -> std::io::Result<File> {
+    OpenOptions::new()
         .append(true)
         .create(true)
-        .open(COORDINATOR_LOG_FILE)
-    {
-        Ok(value) => value,
-        Err(error) => {
-            eprintln!("{}", error);
+        .open(get_coordinator_log_path())
+}
 
-            return Err(NodeSpaceError::CantCreateLogFile(error.to_string()));
+/// the coordinator log, moved to `.1` (and older files shifted) once it reaches `max_size`
+struct RotatingLogFile {
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl RotatingLogFile {
+    fn new(file: File, settings: &LogSettings) -> Self {
+        let size = match file.metadata() {
+            Ok(value) => value.len(),
+            Err(_) => 0,
+        };
+
+        RotatingLogFile {
+            file,
+            size,
+            max_size: settings.max_size,
+            max_files: settings.max_files,
+        }
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            self.file.set_len(0)?;
+            self.size = 0;
+
+            return Ok(());
+        }
+
+        let _ = fs::remove_file(get_rotated_log_path(self.max_files));
+
+        for index in (1..self.max_files).rev() {
+            let path = get_rotated_log_path(index);
+
+            if path.exists() {
+                fs::rename(&path, get_rotated_log_path(index + 1))?;
+            }
+        }
+
+        fs::rename(get_coordinator_log_path(), get_rotated_log_path(1))?;
+
+        self.file = open_log_file()?;
+        self.size = 0;
+
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if writeln!(self.file, "{}", line).is_err() {
+            return;
         }
-    };
 
-    let shared_file = Arc::new(Mutex::new(file));
+        let _ = self.file.flush();
 
-    Ok(shared_file)
+        self.size += line.len() as u64 + 1;
+
+        if self.size < self.max_size {
+            return;
+        }
+
+        if let Err(error) = self.rotate() {
+            let _ = writeln!(self.file, "can't rotate log file: {}", error);
+        }
+    }
 }
 
-pub fn log_to_file(message: &str, shared_file: &LogFile) -> Result<(), NodeSpaceError> {
-    dbg!(message);
+#[derive(Clone)]
+enum LogSink {
+    File(Arc<Mutex<RotatingLogFile>>),
+    Stderr,
+}
 
-    let mut file = match shared_file.lock() {
-        Ok(value) => value,
-        Err(error) => {
-            eprintln!("{}", error);
+/// leveled logger shared by the coordinator threads, cloning it is cheap and every clone writes
+/// to the same sink
+/// writing a log never fails the caller, errors are dropped
+#[derive(Clone)]
+pub struct CoordinatorLogger {
+    sink: LogSink,
+    settings: LogSettings,
+    project: Option<String>,
+}
 
-            return Err(NodeSpaceError::CantWriteLogFile(error.to_string()));
+impl CoordinatorLogger {
+    pub fn new(file: File, settings: LogSettings) -> Self {
+        CoordinatorLogger {
+            sink: LogSink::File(Arc::new(Mutex::new(RotatingLogFile::new(file, &settings)))),
+            settings,
+            project: None,
         }
-    };
+    }
 
-    let effective_message = message.to_string() + "\n";
+    /// logger for the cli side of the coordinator, only warnings and errors are shown unless
+    /// `NODE_SPACE_LOG_LEVEL` asks for more
+    pub fn client() -> Self {
+        CoordinatorLogger {
+            sink: LogSink::Stderr,
+            settings: LogSettings::from_env(LogLevel::Warn),
+            project: None,
+        }
+    }
 
-    match file.write_all(effective_message.as_bytes()) {
-        Ok(_) => {}
-        Err(error) => {
-            eprintln!("{}", error);
+    /// every entry written by the returned logger has the `project` field set to `project`
+    pub fn with_project(&self, project: &str) -> Self {
+        CoordinatorLogger {
+            sink: self.sink.clone(),
+            settings: self.settings,
+            project: Some(project.to_string()),
+        }
+    }
 
-            return Err(NodeSpaceError::CantWriteLogFile(error.to_string()));
+    pub fn log(&self, level: LogLevel, message: &str) {
+        if level > self.settings.level {
+            return;
         }
-    };
 
-    match file.flush() {
-        Ok(_) => {}
+        let entry = LogEntry::new(
+            get_unix_timestamp_millis(),
+            level,
+            self.project.clone(),
+            message.to_string(),
+        );
+
+        let line = match self.settings.format {
+            LogFormat::Text => entry.format(),
+            LogFormat::Json => entry.format_json(),
+        };
+
+        match &self.sink {
+            LogSink::File(file) => {
+                if let Ok(mut file) = file.lock() {
+                    file.write_line(&line);
+                }
+            }
+            LogSink::Stderr => eprintln!("{}", line),
+        }
+    }
+
+    pub fn error(&self, message: &str) {
+        self.log(LogLevel::Error, message);
+    }
+
+    pub fn warn(&self, message: &str) {
+        self.log(LogLevel::Warn, message);
+    }
+
+    pub fn info(&self, message: &str) {
+        self.log(LogLevel::Info, message);
+    }
+
+    pub fn debug(&self, message: &str) {
+        self.log(LogLevel::Debug, message);
+    }
+}
+
+/// keep the log of the last run as the previous session log instead of deleting it
+fn preserve_previous_session_log() {
+    let log_path = get_coordinator_log_path();
+
+    if !log_path.exists() {
+        return;
+    }
+
+    if let Err(error) = fs::rename(log_path, get_coordinator_previous_log_path()) {
+        eprintln!("Error preserving previous logging file: {}", error);
+    }
+}
+
+pub fn create_logging_file(settings: LogSettings) -> Result<CoordinatorLogger, NodeSpaceError> {
+    preserve_previous_session_log();
+
+    let file = match open_log_file() {
+        Ok(value) => value,
         Err(error) => {
             eprintln!("{}", error);
 
-            return Err(NodeSpaceError::CantWriteLogFile(error.to_string()));
+            return Err(NodeSpaceError::CantCreateLogFile(error.to_string()));
         }
     };
 
-    Ok(())
+    Ok(CoordinatorLogger::new(file, settings))
 }
@@ -1,7 +1,10 @@
+pub mod build_log_utils;
+pub mod dependency_graph;
 pub mod handle_coordiantor_logs;
 pub mod handle_signals;
 pub mod handle_start_coordinator;
 pub mod listener_utils;
+pub mod lock_file;
 pub mod log_utils;
 pub mod process_stream_request;
 pub mod socket_file;
@@ -1,57 +1,69 @@
 use std::sync::{Arc, Mutex};
 
-use crate::modals::{coordinator::Coordinator, socket_build_data::SocketBuildData};
+use crate::modals::{
+    coordinator::Coordinator,
+    coordinator_build_scheduler::SchedulerSender,
+    coordinator_control::{ControlMessage, ControlSender},
+    coordinator_message::{
+        CoordinatorRequest, CoordinatorResponse, CoordinatorResponseError, CoordinatorStatus,
+    },
+    coordinator_state::CoordinatorState,
+    coordinator_updates_manager::CoordinatorUpdatesManager,
+    package::Package,
+    socket_build_data::SocketBuildData,
+};
+use crate::time_utils::get_unix_timestamp;
+use crate::watch_coordinator::runtime_dir::get_instance_name;
 
-use super::log_utils::{log_to_file, LogFile};
+use super::log_utils::CoordinatorLogger;
 
-pub fn process_stream_request(
-    shared_lock_coordinator: &Arc<Mutex<Coordinator>>,
-    data_str: &str,
-    shared_logger: &LogFile,
-) {
-    let data: SocketBuildData = match serde_json::from_str(&data_str) {
-        Ok(value) => value,
-        Err(error) => {
-            let _ = log_to_file(
-                &format!(
-                    "error build socket data from message: {},\ndata: {}",
-                    error.to_string(),
-                    &data_str
-                ),
-                shared_logger,
-            );
+/// returns the packages that were not watched before, already watched packages get the settings
+/// of the request
+fn register_project(
+    coordinator: &mut Coordinator,
+    data: SocketBuildData,
+    shared_logger: &CoordinatorLogger,
+) -> Vec<Package> {
+    let mut added_packages = Vec::new();
 
-            return;
-        }
+    let owner = match data.owner {
+        Some(ref value) => value.clone(),
+        None => data.project.path.clone(),
     };
 
-    let mut coordinator = match shared_lock_coordinator.lock() {
-        Ok(value) => value,
-        Err(error) => {
-            let _ = log_to_file(
-                &format!("error getting coordinator: {}", error.to_string()),
-                shared_logger,
-            );
+    let owners = coordinator
+        .registration_owners
+        .entry(data.project.path.clone())
+        .or_default();
 
-            return;
-        }
-    };
+    if !owners.contains(&owner) {
+        owners.push(owner);
+    }
+
+    if !coordinator
+        .registered_projects
+        .iter()
+        .any(|p| p.path == data.project.path)
+    {
+        coordinator.registered_projects.push(data.project.clone());
+    }
 
     for package in data.symlinks.iter() {
-        if coordinator
+        match coordinator
             .watchers_target
-            .iter()
-            .any(|p| p.path == package.path)
+            .iter_mut()
+            .find(|p| p.path == package.path)
         {
-            continue;
-        }
+            Some(value) => *value = package.clone(),
+            None => {
+                shared_logger
+                    .with_project(&package.path)
+                    .info("adding new package to watchers_target");
 
-        let _ = log_to_file(
-            &format!("adding new package to watchers_target: {}", &package.name),
-            shared_logger,
-        );
-
-        coordinator.watchers_target.push(package.clone());
+                coordinator.watchers_target.push(package.clone());
+                added_packages.push(package.clone());
+            }
+        }
 
         let current_entry = coordinator
             .dependencies_to_projects_map
@@ -72,20 +84,454 @@ pub fn process_stream_request(
         }
     }
 
-    if !data.watch_only_links
-        && !coordinator
-            .watchers_target
-            .iter()
-            .any(|p| p.path == data.project.path)
+    if data.watch_only_links {
+        return added_packages;
+    }
+
+    match coordinator
+        .watchers_target
+        .iter_mut()
+        .find(|p| p.path == data.project.path)
     {
-        let _ = log_to_file(
-            &format!(
-                "adding new package to watchers_target: {}",
-                &data.project.name
-            ),
-            shared_logger,
+        Some(value) => *value = data.project,
+        None => {
+            shared_logger
+                .with_project(&data.project.path)
+                .info("adding new package to watchers_target");
+
+            coordinator.watchers_target.push(data.project.clone());
+            added_packages.push(data.project);
+        }
+    }
+
+    added_packages
+}
+
+fn is_known_project(coordinator: &Coordinator, path: &str) -> bool {
+    coordinator
+        .registered_projects
+        .iter()
+        .any(|p| p.path == path)
+        || coordinator.watchers_target.iter().any(|p| p.path == path)
+}
+
+/// registered or attached projects were asked for by a client, or by a project that is still
+/// watched, they are only removed once every owner is gone
+fn is_root_project(coordinator: &Coordinator, path: &str) -> bool {
+    let has_owners = match coordinator.registration_owners.get(path) {
+        Some(value) => !value.is_empty(),
+        None => false,
+    };
+
+    has_owners || coordinator.attached_projects.contains_key(path)
+}
+
+fn attach_project(coordinator: &mut Coordinator, path: &str) {
+    *coordinator
+        .attached_projects
+        .entry(path.to_string())
+        .or_default() += 1;
+}
+
+/// returns true when the last stream attached to the project was closed
+pub fn detach_project(coordinator: &mut Coordinator, path: &str) -> bool {
+    let count = match coordinator.attached_projects.get_mut(path) {
+        Some(value) => value,
+        None => return false,
+    };
+
+    *count -= 1;
+
+    if *count > 0 {
+        return false;
+    }
+
+    coordinator.attached_projects.remove(path);
+
+    true
+}
+
+/// drop `owner` from every registration, returns the projects that lost their last owner and are
+/// not registered anymore
+fn release_registrations(coordinator: &mut Coordinator, owner: &str) -> Vec<String> {
+    let mut released = Vec::new();
+
+    for (path, owners) in coordinator.registration_owners.iter_mut() {
+        let count = owners.len();
+
+        owners.retain(|value| value != owner);
+
+        if owners.is_empty() && count > 0 {
+            released.push(path.clone());
+        }
+    }
+
+    coordinator
+        .registration_owners
+        .retain(|_, owners| !owners.is_empty());
+
+    coordinator
+        .registered_projects
+        .retain(|p| !released.contains(&p.path));
+
+    released
+}
+
+/// remove the project and the registrations made on its behalf, then every dependency that was
+/// only needed by removed projects
+fn unwatch_project(
+    coordinator: &mut Coordinator,
+    path: &str,
+    shared_logger: &CoordinatorLogger,
+) -> CoordinatorResponse {
+    if !is_known_project(coordinator, path) {
+        return CoordinatorResponse::Error(CoordinatorResponseError::UnknownProject(
+            path.to_string(),
+        ));
+    }
+
+    let mut pending = vec![path.to_string()];
+
+    pending.extend(
+        release_registrations(coordinator, path)
+            .into_iter()
+            .filter(|released| released != path),
+    );
+
+    while let Some(current) = pending.pop() {
+        if current != path && is_root_project(coordinator, &current) {
+            continue;
+        }
+
+        let is_needed = match coordinator.dependencies_to_projects_map.get(&current) {
+            Some(value) => !value.is_empty(),
+            None => false,
+        };
+
+        // a package still used by other projects keeps its watcher and its own dependencies
+        if is_needed {
+            continue;
+        }
+
+        shared_logger
+            .with_project(&current)
+            .info("removing package from watchers_target");
+
+        coordinator.watchers_target.retain(|p| p.path != current);
+        coordinator.last_builds.remove(&current);
+        coordinator.content_hashes.remove(&current);
+
+        pending.extend(release_registrations(coordinator, &current));
+
+        let dependencies = match coordinator.projects_to_dependencies_map.remove(&current) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        for dependency in dependencies {
+            let projects = match coordinator
+                .dependencies_to_projects_map
+                .get_mut(&dependency)
+            {
+                Some(value) => value,
+                None => continue,
+            };
+
+            projects.retain(|project| project != &current);
+
+            if projects.is_empty() {
+                coordinator.dependencies_to_projects_map.remove(&dependency);
+                pending.push(dependency);
+            }
+        }
+    }
+
+    CoordinatorResponse::Accepted
+}
+
+/// a failed save is only logged, the coordinator keeps working with the registrations in memory
+fn save_state(coordinator: &Coordinator, shared_logger: &CoordinatorLogger) {
+    if let Err(error) = CoordinatorState::from_coordinator(coordinator).save() {
+        shared_logger.error(&error.to_string());
+    }
+}
+
+/// unwatch the projects that had a `build --watch` stream attached to the previous coordinator,
+/// the clients lost their stream when it stopped
+pub fn release_attached_projects(
+    coordinator: &mut Coordinator,
+    paths: &[String],
+    shared_logger: &CoordinatorLogger,
+) {
+    for path in paths {
+        shared_logger
+            .with_project(path)
+            .info("attached client is gone, unwatching project");
+
+        unwatch_project(coordinator, path, shared_logger);
+    }
+}
+
+pub fn process_stream_request(
+    shared_lock_coordinator: &Arc<Mutex<Coordinator>>,
+    request: CoordinatorRequest,
+    scheduler: &SchedulerSender,
+    control: &ControlSender,
+    shared_logger: &CoordinatorLogger,
+) -> CoordinatorResponse {
+    let mut coordinator = match shared_lock_coordinator.lock() {
+        Ok(value) => value,
+        Err(error) => {
+            let message = format!("error getting coordinator: {}", error);
+
+            shared_logger.error(&message);
+
+            return CoordinatorResponse::Error(CoordinatorResponseError::Internal(message));
+        }
+    };
+
+    match request {
+        CoordinatorRequest::Register(data) => {
+            let added_packages = register_project(&mut coordinator, *data, shared_logger);
+            save_state(&coordinator, shared_logger);
+
+            let _ = control.send(ControlMessage::SyncWatchers);
+
+            // the coordinator only builds on changes unless a package asks for a first build
+            let build_paths = added_packages
+                .iter()
+                .filter(|package| package.build_on_start.unwrap_or(false))
+                .map(|package| package.path.clone())
+                .collect::<Vec<String>>();
+
+            if !build_paths.is_empty() {
+                CoordinatorUpdatesManager::request_builds(
+                    &coordinator,
+                    build_paths,
+                    scheduler,
+                    shared_logger,
+                );
+            }
+
+            CoordinatorResponse::Accepted
+        }
+        CoordinatorRequest::Unwatch { path } => {
+            let response = unwatch_project(&mut coordinator, &path, shared_logger);
+
+            if response.is_accepted() {
+                save_state(&coordinator, shared_logger);
+            }
+
+            let _ = control.send(ControlMessage::SyncWatchers);
+
+            response
+        }
+        // the listener keeps the stream open and unwatches the project once it is closed
+        CoordinatorRequest::Attach { path } => match is_known_project(&coordinator, &path) {
+            true => {
+                attach_project(&mut coordinator, &path);
+                save_state(&coordinator, shared_logger);
+
+                CoordinatorResponse::Accepted
+            }
+            false => CoordinatorResponse::Error(CoordinatorResponseError::UnknownProject(path)),
+        },
+        CoordinatorRequest::Status => CoordinatorResponse::Status(Box::new(CoordinatorStatus {
+            pid: std::process::id(),
+            instance: get_instance_name(),
+            max_jobs: coordinator.max_jobs,
+            started_at: coordinator.started_at,
+            uptime_seconds: get_unix_timestamp().saturating_sub(coordinator.started_at),
+            registered_projects: coordinator.registered_projects.clone(),
+            registration_owners: coordinator.registration_owners.clone(),
+            attached_projects: coordinator.attached_projects.keys().cloned().collect(),
+            watchers_target: coordinator.watchers_target.clone(),
+            active_watchers: coordinator.active_watchers.clone(),
+            projects_to_dependencies_map: coordinator.projects_to_dependencies_map.clone(),
+            last_builds: coordinator.last_builds.clone(),
+        })),
+        CoordinatorRequest::List => CoordinatorResponse::List(coordinator.watchers_target.clone()),
+        CoordinatorRequest::Stop => {
+            shared_logger.info("got stop request");
+
+            let _ = control.send(ControlMessage::Stop(String::from("stop request")));
+
+            CoordinatorResponse::Accepted
+        }
+        CoordinatorRequest::TriggerBuild { path } => {
+            if !coordinator.watchers_target.iter().any(|p| p.path == path) {
+                return CoordinatorResponse::Error(CoordinatorResponseError::UnknownProject(path));
+            }
+
+            CoordinatorUpdatesManager::request_builds(
+                &coordinator,
+                vec![path],
+                scheduler,
+                shared_logger,
+            );
+
+            CoordinatorResponse::Accepted
+        }
+        // the listener keeps the stream open for subscribers
+        CoordinatorRequest::Subscribe => CoordinatorResponse::Subscribed,
+        CoordinatorRequest::Ping => CoordinatorResponse::Pong {
+            pid: std::process::id(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modals::coordinator::Coordinator;
+    use crate::modals::log_entry::LogLevel;
+    use crate::watch_coordinator::coordinator::log_utils::LogSettings;
+
+    fn create_package(path: &str) -> Package {
+        Package {
+            name: path.trim_start_matches('/').to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_data(project: &str, links: &[&str], watch_only_links: bool) -> SocketBuildData {
+        SocketBuildData::new(
+            links.iter().map(|link| create_package(link)).collect(),
+            create_package(project),
+            watch_only_links,
+        )
+    }
+
+    fn register(coordinator: &mut Coordinator, project: &str, links: &[&str]) {
+        let data = create_data(project, links, false);
+
+        register_project(coordinator, data, &CoordinatorLogger::client());
+    }
+
+    fn is_watched(coordinator: &Coordinator, path: &str) -> bool {
+        coordinator.watchers_target.iter().any(|p| p.path == path)
+    }
+
+    fn create_coordinator() -> Coordinator {
+        Coordinator::new(1, LogSettings::from_env(LogLevel::Error), true)
+    }
+
+    #[test]
+    fn unwatch_keeps_dependencies_of_a_needed_package() {
+        let mut coordinator = create_coordinator();
+        let logger = CoordinatorLogger::client();
+
+        register(&mut coordinator, "/app1", &["/package2"]);
+        register(&mut coordinator, "/package2", &["/package1"]);
+
+        unwatch_project(&mut coordinator, "/package2", &logger);
+
+        assert!(is_watched(&coordinator, "/package2"));
+        assert!(is_watched(&coordinator, "/package1"));
+        assert_eq!(
+            coordinator.dependencies_to_projects_map.get("/package1"),
+            Some(&vec![String::from("/package2")])
         );
 
-        coordinator.watchers_target.push(data.project)
+        unwatch_project(&mut coordinator, "/app1", &logger);
+
+        assert!(coordinator.watchers_target.is_empty());
+        assert!(coordinator.dependencies_to_projects_map.is_empty());
+        assert!(coordinator.projects_to_dependencies_map.is_empty());
+    }
+
+    #[test]
+    fn unwatch_does_not_cascade_into_registered_or_attached_projects() {
+        let mut coordinator = create_coordinator();
+        let logger = CoordinatorLogger::client();
+
+        register(&mut coordinator, "/app1", &["/package1", "/package2"]);
+        register(&mut coordinator, "/package1", &[]);
+        attach_project(&mut coordinator, "/package2");
+
+        unwatch_project(&mut coordinator, "/app1", &logger);
+
+        assert!(!is_watched(&coordinator, "/app1"));
+        assert!(is_watched(&coordinator, "/package1"));
+        assert!(is_watched(&coordinator, "/package2"));
+
+        unwatch_project(&mut coordinator, "/package1", &logger);
+
+        assert!(!is_watched(&coordinator, "/package1"));
+    }
+
+    #[test]
+    fn unwatch_removes_the_registrations_made_for_the_project() {
+        let mut coordinator = create_coordinator();
+        let logger = CoordinatorLogger::client();
+
+        // the requests sent by `handle_watch_project_with_dependencies`
+        for data in [
+            create_data("/package2", &["/package1"], false).with_owner("/app1"),
+            create_data("/package1", &["/package0"], false).with_owner("/app1"),
+            create_data("/app1", &["/package2"], true),
+        ] {
+            register_project(&mut coordinator, data, &logger);
+        }
+
+        assert!(is_watched(&coordinator, "/package0"));
+
+        unwatch_project(&mut coordinator, "/app1", &logger);
+
+        assert!(coordinator.watchers_target.is_empty());
+        assert!(coordinator.registered_projects.is_empty());
+        assert!(coordinator.registration_owners.is_empty());
+        assert!(coordinator.dependencies_to_projects_map.is_empty());
+        assert!(coordinator.projects_to_dependencies_map.is_empty());
+    }
+
+    #[test]
+    fn closing_one_of_two_attached_clients_keeps_the_project() {
+        let mut coordinator = create_coordinator();
+
+        register(&mut coordinator, "/app1", &["/package1"]);
+        attach_project(&mut coordinator, "/app1");
+        attach_project(&mut coordinator, "/app1");
+
+        assert!(!detach_project(&mut coordinator, "/app1"));
+        assert_eq!(coordinator.attached_projects.get("/app1"), Some(&1));
+
+        assert!(detach_project(&mut coordinator, "/app1"));
+        assert!(coordinator.attached_projects.is_empty());
+        assert!(!detach_project(&mut coordinator, "/app1"));
+    }
+
+    #[test]
+    fn restoring_the_state_drops_the_registrations_of_attached_projects() {
+        let mut coordinator = create_coordinator();
+        let logger = CoordinatorLogger::client();
+
+        for data in [
+            create_data("/package1", &["/package0"], false).with_owner("/app1"),
+            create_data("/app1", &["/package1"], true),
+            create_data("/app2", &["/package0"], false),
+        ] {
+            register_project(&mut coordinator, data, &logger);
+        }
+
+        attach_project(&mut coordinator, "/app1");
+
+        let state = CoordinatorState::from_coordinator(&coordinator);
+        let attached_projects = state.attached_projects.clone();
+        let mut restored = create_coordinator();
+
+        state.apply(&mut restored);
+        release_attached_projects(&mut restored, &attached_projects, &logger);
+
+        assert!(restored.attached_projects.is_empty());
+        assert!(!is_known_project(&restored, "/app1"));
+        assert!(!is_known_project(&restored, "/package1"));
+        assert!(is_watched(&restored, "/app2"));
+        assert!(is_watched(&restored, "/package0"));
+        assert_eq!(
+            restored.dependencies_to_projects_map.get("/package0"),
+            Some(&vec![String::from("/app2")])
+        );
     }
 }
@@ -1,47 +1,39 @@
-use std::{fs::OpenOptions, path::Path};
-
 use crate::{
-    errors::node_space::NodeSpaceError,
-    watch_coordinator::{
-        client::constants::COORDINATOR_SOCKET_PATH, coordinator::log_utils::log_to_file,
-    },
+    errors::node_space::NodeSpaceError, watch_coordinator::runtime_dir::get_coordinator_socket_path,
 };
 
-use super::log_utils::LogFile;
+use super::log_utils::CoordinatorLogger;
+
+pub fn delete_socket_file(shared_logger: &CoordinatorLogger) {
+    let socket_path = get_coordinator_socket_path();
 
-pub fn delete_socket_file(shared_logger: &LogFile) {
-    if !Path::new(&COORDINATOR_SOCKET_PATH).exists() {
+    if !socket_path.exists() {
         return;
     }
 
-    let removed_result = std::fs::remove_file(&COORDINATOR_SOCKET_PATH);
+    let removed_result = std::fs::remove_file(&socket_path);
 
     if removed_result.is_err() {
-        let _ = log_to_file("Error removing socket file", shared_logger);
+        shared_logger.warn("Error removing socket file");
     }
 }
 
-pub fn create_socket_file(shared_logger: &LogFile) -> Result<(), NodeSpaceError> {
-    if Path::new(&COORDINATOR_SOCKET_PATH).exists() {
-        delete_socket_file(shared_logger);
-
-        return Ok(());
-    }
+/// clear the socket path so the listener can bind to it, binding creates the socket file
+pub fn create_socket_file(shared_logger: &CoordinatorLogger) -> Result<(), NodeSpaceError> {
+    let socket_path = get_coordinator_socket_path();
 
-    match OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(&COORDINATOR_SOCKET_PATH)
-    {
-        Ok(value) => value,
-        Err(error) => {
-            eprintln!("{}", error);
+    if socket_path.exists() {
+        shared_logger.info("removing stale socket file");
 
-            return Err(NodeSpaceError::CantCreateSocketFile(error.to_string()));
-        }
-    };
+        delete_socket_file(shared_logger);
+    }
 
-    log_to_file("created socket file", shared_logger)?;
+    if socket_path.exists() {
+        return Err(NodeSpaceError::CantCreateSocketFile(format!(
+            "can't remove old socket file: {}",
+            socket_path.display()
+        )));
+    }
 
     Ok(())
 }
@@ -1,38 +1,58 @@
-use serde_json;
-
-use std::io::Write;
-use std::os::unix::net::UnixStream;
+use std::{os::unix::net::UnixStream, time::Duration};
 
 use crate::{
-    errors::{node_space::NodeSpaceError, socket::SocketError},
-    modals::socket_build_data::SocketBuildData,
-    retry::retry,
+    errors::node_space::NodeSpaceError,
+    modals::{coordinator_message::CoordinatorRequest, socket_build_data::SocketBuildData},
 };
 
-use super::client::constants::COORDINATOR_SOCKET_PATH;
-
-// TODO: check if build success?
-pub fn send_data_to_coordinator(data: SocketBuildData) -> Result<(), NodeSpaceError> {
-    let stream = retry(
-        || match UnixStream::connect(COORDINATOR_SOCKET_PATH) {
-            Ok(value) => Ok(value),
-            Err(error) => Err(SocketError::ErrorConnectingToSocket(error.to_string()).into()),
-        },
-        None,
-        None,
-    );
+use super::client::{
+    coordinator_request::{check_response, connect_to_coordinator, send_request_on_stream},
+    start_coordinator::start_coordinator,
+};
 
-    let mut stream = stream.unwrap();
+const REGISTER_TIMEOUT: Option<Duration> = Some(Duration::from_secs(2));
 
-    let project_str = match serde_json::to_string(&data) {
+/// a coordinator that is gone is started again instead of waiting for the socket to come back
+fn connect_or_start_coordinator() -> Result<UnixStream, NodeSpaceError> {
+    let stream = match connect_to_coordinator() {
         Ok(value) => value,
-        Err(error) => return Err(SocketError::ErrorSendingDataInSocket(error.to_string()).into()),
-    };
+        Err(_) => {
+            start_coordinator(None)?;
 
-    match writeln!(stream, "{}", project_str) {
-        Ok(_) => (),
-        Err(error) => return Err(SocketError::ErrorSendingDataInSocket(error.to_string()).into()),
+            connect_to_coordinator()?
+        }
     };
 
+    let _ = stream.set_read_timeout(REGISTER_TIMEOUT);
+    let _ = stream.set_write_timeout(REGISTER_TIMEOUT);
+
+    Ok(stream)
+}
+
+/// register the project and its links with the coordinator, fails if the coordinator rejects it
+pub fn send_data_to_coordinator(data: SocketBuildData) -> Result<(), NodeSpaceError> {
+    let stream = connect_or_start_coordinator()?;
+
+    let response = send_request_on_stream(&stream, CoordinatorRequest::Register(Box::new(data)))?;
+
+    check_response(response)?;
+
     Ok(())
 }
+
+/// keep a stream open for as long as the returned value lives, the coordinator unwatches the
+/// project once it is dropped or the process exits
+pub fn attach_to_coordinator(path: &str) -> Result<UnixStream, NodeSpaceError> {
+    let stream = connect_to_coordinator()?;
+
+    let response = send_request_on_stream(
+        &stream,
+        CoordinatorRequest::Attach {
+            path: path.to_string(),
+        },
+    )?;
+
+    check_response(response)?;
+
+    Ok(stream)
+}
@@ -1,3 +1,4 @@
 pub mod client;
 pub mod coordinator;
 pub mod coordinator_communication;
+pub mod runtime_dir;
@@ -0,0 +1,252 @@
+use std::{
+    env,
+    fs::{self, DirBuilder},
+    os::unix::{
+        ffi::OsStrExt,
+        fs::{DirBuilderExt, MetadataExt, PermissionsExt},
+    },
+    path::{Path, PathBuf},
+};
+
+use crate::errors::node_space::NodeSpaceError;
+
+/// overrides the directory that holds the dirs of the coordinator instances
+pub const RUNTIME_DIR_ENV: &str = "NODE_SPACE_RUNTIME_DIR";
+
+/// name of the coordinator instance, every instance has its own daemon, socket, pid and logs
+pub const INSTANCE_ENV: &str = "NODE_SPACE_INSTANCE";
+pub const DEFAULT_INSTANCE: &str = "default";
+
+pub const SOCKET_FILE_NAME: &str = "coordinator.sock";
+pub const PID_FILE_NAME: &str = "coordinator.pid";
+pub const LOCK_FILE_NAME: &str = "coordinator.lock";
+
+const RUNTIME_DIR_MODE: u32 = 0o700;
+
+/// `$NODE_SPACE_RUNTIME_DIR`, then `$XDG_RUNTIME_DIR/node-space`, then `/tmp/node-space-<uid>`
+pub fn get_runtime_dir() -> PathBuf {
+    if let Ok(value) = env::var(RUNTIME_DIR_ENV) {
+        if !value.is_empty() {
+            return PathBuf::from(value);
+        }
+    }
+
+    if let Ok(value) = env::var("XDG_RUNTIME_DIR") {
+        if !value.is_empty() {
+            return PathBuf::from(value).join("node-space");
+        }
+    }
+
+    let uid = unsafe { libc::getuid() };
+
+    env::temp_dir().join(format!("node-space-{}", uid))
+}
+
+/// only dirs created here get the 0700 mode, an existing dir is left as is and rejected when it
+/// is not private
+fn create_private_dir(dir: &Path) -> Result<(), NodeSpaceError> {
+    if !dir.exists() {
+        if let Err(error) = DirBuilder::new()
+            .recursive(true)
+            .mode(RUNTIME_DIR_MODE)
+            .create(dir)
+        {
+            return Err(NodeSpaceError::CantCreateRuntimeDir(format!(
+                "{}: {}",
+                dir.display(),
+                error
+            )));
+        }
+    }
+
+    let metadata = match fs::metadata(dir) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(NodeSpaceError::CantCreateRuntimeDir(format!(
+                "{}: {}",
+                dir.display(),
+                error
+            )))
+        }
+    };
+
+    if !metadata.is_dir() {
+        return Err(NodeSpaceError::CantCreateRuntimeDir(format!(
+            "{} is not a directory",
+            dir.display()
+        )));
+    }
+
+    let uid = unsafe { libc::getuid() };
+
+    if metadata.uid() != uid {
+        return Err(NodeSpaceError::CantCreateRuntimeDir(format!(
+            "{} is owned by another user",
+            dir.display()
+        )));
+    }
+
+    if metadata.permissions().mode() & 0o022 != 0 {
+        return Err(NodeSpaceError::CantCreateRuntimeDir(format!(
+            "{} is writable by other users",
+            dir.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// create the runtime dir and the dir of the current instance with 0700 permissions, existing
+/// dirs must belong to the current user and must not be writable by others so nobody else can
+/// replace the coordinator socket
+pub fn ensure_runtime_dir() -> Result<PathBuf, NodeSpaceError> {
+    create_private_dir(&get_runtime_dir())?;
+
+    let instance_dir = get_instance_dir();
+
+    create_private_dir(&instance_dir)?;
+
+    Ok(instance_dir)
+}
+
+/// instance names are used as a dir name, only letters, digits, `-`, `_` and `.` are allowed
+pub fn is_valid_instance_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// the closest parent of `path` with a `.git` entry
+pub fn get_workspace_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// fnv-1a, stable between builds unlike the std hasher
+pub fn hash_path(path: &Path) -> u32 {
+    path.as_os_str()
+        .as_bytes()
+        .iter()
+        .fold(0x811c9dc5, |hash: u32, byte| {
+            (hash ^ *byte as u32).wrapping_mul(0x01000193)
+        })
+}
+
+/// `/home/user/dev/my-repo` -> `my-repo-1a2b3c4d`, the hash keeps repos with the same dir name
+/// apart
+pub fn derive_instance_name(workspace_root: &Path) -> String {
+    let dir_name = match workspace_root.file_name() {
+        Some(value) => value
+            .to_string_lossy()
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .collect::<String>(),
+        None => DEFAULT_INSTANCE.to_string(),
+    };
+
+    format!("{}-{:08x}", dir_name, hash_path(workspace_root))
+}
+
+/// `$NODE_SPACE_INSTANCE`, then derived from the workspace root of the current dir, then
+/// `default` outside of a workspace
+pub fn get_instance_name() -> String {
+    if let Ok(value) = env::var(INSTANCE_ENV) {
+        if is_valid_instance_name(&value) {
+            return value;
+        }
+    }
+
+    let workspace_root = match env::current_dir() {
+        Ok(value) => get_workspace_root(&value),
+        Err(_) => None,
+    };
+
+    match workspace_root {
+        Some(value) => derive_instance_name(&value),
+        None => DEFAULT_INSTANCE.to_string(),
+    }
+}
+
+/// pin the instance for this process and the coordinator it spawns, the env var is inherited by
+/// the child so it does not depend on the dir it runs in
+pub fn select_instance(instance: Option<&str>) -> Result<String, NodeSpaceError> {
+    let name = match instance {
+        Some(value) => value.to_string(),
+        None => get_instance_name(),
+    };
+
+    if !is_valid_instance_name(&name) {
+        return Err(NodeSpaceError::InvalidInstanceName(name));
+    }
+
+    env::set_var(INSTANCE_ENV, &name);
+
+    Ok(name)
+}
+
+pub fn get_instance_dir_of(instance: &str) -> PathBuf {
+    get_runtime_dir().join(instance)
+}
+
+pub fn get_instance_dir() -> PathBuf {
+    get_instance_dir_of(&get_instance_name())
+}
+
+/// names of the instances that have a dir in the runtime dir, running or not
+pub fn list_instance_names() -> Vec<String> {
+    let entries = match fs::read_dir(get_runtime_dir()) {
+        Ok(value) => value,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut names = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_instance_name(name))
+        .collect::<Vec<String>>();
+
+    names.sort();
+
+    names
+}
+
+pub fn get_coordinator_socket_path() -> PathBuf {
+    get_instance_dir().join(SOCKET_FILE_NAME)
+}
+
+pub fn get_coordinator_pid_path() -> PathBuf {
+    get_instance_dir().join(PID_FILE_NAME)
+}
+
+pub fn get_coordinator_lock_path() -> PathBuf {
+    get_instance_dir().join(LOCK_FILE_NAME)
+}
+
+pub fn get_coordinator_log_path() -> PathBuf {
+    get_instance_dir().join("coordinator.log")
+}
+
+/// registrations of the coordinator, reloaded when it starts again
+pub fn get_coordinator_state_path() -> PathBuf {
+    get_instance_dir().join("state.json")
+}
+
+/// the log of the last coordinator run, kept so a crash can be inspected after a restart
+pub fn get_coordinator_previous_log_path() -> PathBuf {
+    get_instance_dir().join("coordinator.previous.log")
+}
+
+/// output of the last coordinator build of every project, one file per project
+pub fn get_build_logs_dir() -> PathBuf {
+    get_instance_dir().join("builds")
+}
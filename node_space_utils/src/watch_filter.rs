@@ -0,0 +1,686 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{modals::package::Package, package_utils::get_package_json_data};
+
+/// folders and files that never trigger a build, whatever the project config says
+const DEFAULT_IGNORED_DIRS: [&str; 8] = [
+    "node_modules",
+    ".git",
+    ".turbo",
+    ".cache",
+    ".next",
+    ".nuxt",
+    ".parcel-cache",
+    "coverage",
+];
+
+/// editor swap and backup files
+const DEFAULT_IGNORED_FILES: [&str; 7] =
+    ["*.swp", "*.swo", "*.swx", "*~", ".#*", "4913", ".DS_Store"];
+
+/// package.json fields that point at what a package publishes
+const PUBLISHED_FIELDS: [&str; 7] = [
+    "files", "main", "module", "browser", "types", "typings", "exports",
+];
+
+/// files of a library that don't change what its consumers get
+const NON_PUBLISHED_FILES: [&str; 7] = [
+    "*.test.*",
+    "*.spec.*",
+    "*.stories.*",
+    "*.story.*",
+    "__tests__/",
+    "__mocks__/",
+    "__snapshots__/",
+];
+
+/// `*` and `?` match inside a path segment, `**` matches any number of segments
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    match_bytes(pattern.as_bytes(), text.as_bytes())
+}
+
+fn match_bytes(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = &pattern[2..];
+
+            let rest = match rest.first() {
+                Some(b'/') => &rest[1..],
+                _ => rest,
+            };
+
+            if rest.is_empty() {
+                return true;
+            }
+
+            (0..=text.len())
+                .filter(|index| *index == 0 || text[index - 1] == b'/')
+                .any(|index| match_bytes(rest, &text[index..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+
+            for index in 0..=text.len() {
+                if match_bytes(rest, &text[index..]) {
+                    return true;
+                }
+
+                if index < text.len() && text[index] == b'/' {
+                    return false;
+                }
+            }
+
+            false
+        }
+        Some(b'?') => match text.first() {
+            Some(b'/') | None => false,
+            Some(_) => match_bytes(&pattern[1..], &text[1..]),
+        },
+        Some(character) => match text.first() {
+            Some(value) if value == character => match_bytes(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+    }
+}
+
+/// a gitignore style pattern, also used for the include and exclude globs of a package:
+/// patterns without a `/` match the name at any depth, the others match from the package root
+#[derive(Debug, Clone)]
+struct WatchPattern {
+    glob: String,
+    is_anchored: bool,
+    is_dir_only: bool,
+    is_negated: bool,
+}
+
+impl WatchPattern {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (is_negated, line) = match line.strip_prefix('!') {
+            Some(value) => (true, value),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+
+        let (is_dir_only, line) = match line.strip_suffix('/') {
+            Some(value) => (true, value),
+            None => (false, line),
+        };
+
+        let is_anchored = line.contains('/');
+        let glob = line.trim_start_matches('/').to_string();
+
+        if glob.is_empty() {
+            return None;
+        }
+
+        Some(WatchPattern {
+            glob,
+            is_anchored,
+            is_dir_only,
+            is_negated,
+        })
+    }
+
+    /// `path` is relative to the package root and uses `/` separators
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.is_dir_only && !is_dir {
+            return false;
+        }
+
+        if self.is_anchored {
+            return glob_match(&self.glob, path);
+        }
+
+        let name = match path.rsplit_once('/') {
+            Some((_, value)) => value,
+            None => path,
+        };
+
+        glob_match(&self.glob, name)
+    }
+}
+
+fn parse_patterns<'a, I: Iterator<Item = &'a str>>(lines: I) -> Vec<WatchPattern> {
+    lines.filter_map(WatchPattern::parse).collect()
+}
+
+/// the last matching pattern decides, like in a gitignore file, `None` when none of them match
+fn get_match(patterns: &[WatchPattern], path: &str, is_dir: bool) -> Option<bool> {
+    patterns
+        .iter()
+        .rev()
+        .find(|pattern| pattern.matches(path, is_dir))
+        .map(|pattern| !pattern.is_negated)
+}
+
+fn is_matching(patterns: &[WatchPattern], path: &str, is_dir: bool) -> bool {
+    get_match(patterns, path, is_dir) == Some(true)
+}
+
+/// the rules of a `.gitignore` file, they apply to the paths below the folder of the file
+#[derive(Debug, Clone)]
+struct IgnoreFile {
+    /// folder of the file relative to the package root, empty for the package root and its
+    /// parents
+    dir: String,
+    /// the package root relative to the folder of the file, empty unless the file is in a parent
+    package_dir: String,
+    patterns: Vec<WatchPattern>,
+}
+
+impl IgnoreFile {
+    fn read(folder: &Path, dir: String, package_dir: String) -> Option<Self> {
+        let content = fs::read_to_string(folder.join(".gitignore")).ok()?;
+
+        Some(IgnoreFile {
+            dir,
+            package_dir,
+            patterns: parse_patterns(content.lines()),
+        })
+    }
+
+    /// the path as the rules of the file see it, `None` when the path is not below the file
+    fn get_rule_path(&self, path: &str) -> Option<String> {
+        let path = match self.dir.is_empty() {
+            true => path,
+            false => path.strip_prefix(&self.dir)?.strip_prefix('/')?,
+        };
+
+        match self.package_dir.is_empty() {
+            true => Some(path.to_string()),
+            false => Some(format!("{}/{}", self.package_dir, path)),
+        }
+    }
+
+    fn get_match(&self, path: &str, is_dir: bool) -> Option<bool> {
+        get_match(&self.patterns, &self.get_rule_path(path)?, is_dir)
+    }
+}
+
+/// `.gitignore` files of the package root and of its parents up to the git root, outermost first
+/// so the rules closer to the package win
+fn read_ignore_files(root: &Path) -> Vec<IgnoreFile> {
+    let git_root = root.ancestors().find(|folder| folder.join(".git").exists());
+
+    let mut folders: Vec<&Path> = match git_root {
+        Some(git_root) => root
+            .ancestors()
+            .take_while(|folder| folder.starts_with(git_root))
+            .collect(),
+        None => vec![root],
+    };
+
+    folders.reverse();
+
+    folders
+        .into_iter()
+        .filter_map(|folder| {
+            let package_dir = root
+                .strip_prefix(folder)
+                .ok()?
+                .to_string_lossy()
+                .to_string();
+
+            IgnoreFile::read(folder, String::new(), package_dir)
+        })
+        .collect()
+}
+
+fn collect_json_paths(value: &Value, paths: &mut Vec<String>) {
+    match value {
+        Value::String(path) => paths.push(path.clone()),
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_json_paths(value, paths)),
+        Value::Object(map) => map
+            .values()
+            .for_each(|value| collect_json_paths(value, paths)),
+        _ => {}
+    }
+}
+
+/// the sources behind what the package publishes, as anchored include globs:
+/// entries of `files` outside of the output folder are watched as they are, the other fields
+/// point at entry files so their whole top level folder is watched, and entries inside the output
+/// folder are built from `src`, which is watched whenever it exists.
+/// empty when package.json says nothing useful or an entry file sits at the package root, then
+/// the whole package is watched
+pub fn get_published_patterns(package: &Package) -> Vec<String> {
+    let package_json_data = match get_package_json_data(&package.path) {
+        Ok(value) => value,
+        Err(_) => return Vec::new(),
+    };
+
+    let output_name = package.output_name.trim_matches('/');
+    let has_src = Path::new(&package.path).join("src").is_dir();
+
+    let mut patterns: Vec<String> = Vec::new();
+
+    let mut add_entry = |entry: &str| {
+        for pattern in [format!("/{}", entry), format!("/{}/**", entry)] {
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+    };
+
+    for field in PUBLISHED_FIELDS {
+        let mut entries = Vec::new();
+
+        if let Some(value) = package_json_data.get(field) {
+            collect_json_paths(value, &mut entries);
+        }
+
+        for entry in entries.iter() {
+            // negated `files` entries and `exports` conditions like `null` don't add sources
+            if entry.starts_with('!') {
+                continue;
+            }
+
+            let entry = entry.trim_start_matches("./").trim_matches('/');
+
+            if entry.is_empty() || entry == "." || entry.starts_with("..") {
+                continue;
+            }
+
+            let is_output = entry == output_name || entry.starts_with(&format!("{}/", output_name));
+
+            if is_output {
+                continue;
+            }
+
+            if field == "files" {
+                add_entry(entry);
+
+                continue;
+            }
+
+            match entry.split_once('/') {
+                Some((folder, _)) => add_entry(folder),
+                None => return Vec::new(),
+            }
+        }
+    }
+
+    if has_src {
+        add_entry("src");
+    }
+
+    if patterns.is_empty() {
+        return patterns;
+    }
+
+    patterns.push("/package.json".to_string());
+
+    patterns
+}
+
+/// decides which file changes of a package are worth a rebuild
+#[derive(Debug, Clone)]
+pub struct WatchFilter {
+    root: PathBuf,
+    output_name: String,
+    ignored: Vec<WatchPattern>,
+    ignore_files: Vec<IgnoreFile>,
+    /// `.gitignore` files of the folders inside the package, read the first time a path below
+    /// them is checked
+    nested_ignore_files: RefCell<HashMap<String, Option<IgnoreFile>>>,
+    include: Vec<WatchPattern>,
+    exclude: Vec<WatchPattern>,
+}
+
+impl WatchFilter {
+    /// reads the `.gitignore` files from the git root down to the package and inside of it,
+    /// missing files ignore nothing
+    pub fn new(package: &Package) -> Self {
+        let root = PathBuf::from(&package.path);
+
+        let ignored = DEFAULT_IGNORED_FILES
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .chain(DEFAULT_IGNORED_DIRS.iter().map(|dir| format!("{}/", dir)))
+            .filter_map(|line| WatchPattern::parse(&line))
+            .collect();
+
+        WatchFilter {
+            ignore_files: read_ignore_files(&root),
+            root,
+            output_name: package.output_name.clone(),
+            ignored,
+            nested_ignore_files: RefCell::new(HashMap::new()),
+            include: parse_patterns(package.include.iter().map(String::as_str)),
+            exclude: parse_patterns(package.exclude.iter().map(String::as_str)),
+        }
+    }
+
+    /// for linked packages, only the published sources count and tests or stories next to them
+    /// don't, `include` globs of the package replace the derived watch set
+    pub fn published(package: &Package) -> Self {
+        let mut filter = Self::new(package);
+
+        if !package.include.is_empty() {
+            return filter;
+        }
+
+        let include = get_published_patterns(package);
+
+        if include.is_empty() {
+            return filter;
+        }
+
+        filter.include = parse_patterns(include.iter().map(String::as_str));
+        filter
+            .exclude
+            .extend(parse_patterns(NON_PUBLISHED_FILES.into_iter()));
+
+        filter
+    }
+
+    fn get_relative_path(&self, path: &Path) -> Option<Vec<String>> {
+        let relative_path = path.strip_prefix(&self.root).ok()?;
+
+        relative_path
+            .components()
+            .map(|component| match component {
+                Component::Normal(value) => value.to_str().map(str::to_string),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// the rules of every `.gitignore` file that applies to the path, the deepest file decides
+    fn is_git_ignored(&self, segments: &[String], is_dir: bool) -> bool {
+        let path = segments.join("/");
+        let mut state = get_match(&self.ignored, &path, is_dir);
+
+        for file in self.ignore_files.iter() {
+            if let Some(value) = file.get_match(&path, is_dir) {
+                state = Some(value);
+            }
+        }
+
+        let mut nested_ignore_files = self.nested_ignore_files.borrow_mut();
+
+        for length in 1..segments.len() {
+            let dir = segments[..length].join("/");
+
+            let file = nested_ignore_files.entry(dir.clone()).or_insert_with(|| {
+                IgnoreFile::read(&self.root.join(&dir), dir.clone(), String::new())
+            });
+
+            if let Some(value) = file.as_ref().and_then(|file| file.get_match(&path, is_dir)) {
+                state = Some(value);
+            }
+        }
+
+        state == Some(true)
+    }
+
+    /// a path is ignored when it or one of its parent folders is ignored
+    fn is_ignored(&self, segments: &[String], is_dir: bool) -> bool {
+        (1..=segments.len()).any(|length| {
+            let path = segments[..length].join("/");
+            let is_path_dir = length < segments.len() || is_dir;
+
+            self.is_git_ignored(&segments[..length], is_path_dir)
+                || is_matching(&self.exclude, &path, is_path_dir)
+        })
+    }
+
+    /// a path is included when it or one of its parent folders is, the deepest match decides so
+    /// `!src/generated` takes a folder out of `src` again
+    fn is_included(&self, segments: &[String], is_dir: bool) -> bool {
+        let mut state = None;
+
+        for length in 1..=segments.len() {
+            let path = segments[..length].join("/");
+            let is_path_dir = length < segments.len() || is_dir;
+
+            if let Some(value) = get_match(&self.include, &path, is_path_dir) {
+                state = Some(value);
+            }
+        }
+
+        state == Some(true)
+    }
+
+    /// outside of the package, in its output folder, ignored by git or by the package excludes,
+    /// folders are skipped as a whole when this is true
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let segments = match self.get_relative_path(path) {
+            Some(value) => value,
+            None => return true,
+        };
+
+        if segments.is_empty() {
+            return true;
+        }
+
+        let relative_path = segments.join("/");
+        let output_name = self.output_name.trim_matches('/');
+
+        if relative_path == output_name || relative_path.starts_with(&format!("{}/", output_name)) {
+            return true;
+        }
+
+        self.is_ignored(&segments, path.is_dir())
+    }
+
+    /// changes to excluded paths or paths not matching the includes of the package are not
+    /// relevant
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        if self.is_excluded(path) {
+            return false;
+        }
+
+        if self.include.is_empty() {
+            return true;
+        }
+
+        let segments = match self.get_relative_path(path) {
+            Some(value) => value,
+            None => return false,
+        };
+
+        self.is_included(&segments, path.is_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_package(path: &Path, include: &[&str], exclude: &[&str]) -> Package {
+        Package {
+            name: String::from("app"),
+            path: path.to_string_lossy().to_string(),
+            output_name: String::from("dist"),
+            include: include.iter().map(|value| value.to_string()).collect(),
+            exclude: exclude.iter().map(|value| value.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "node-space-watch-filter-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn write_file(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn is_pattern_matching(lines: &[&str], path: &str, is_dir: bool) -> bool {
+        is_matching(&parse_patterns(lines.iter().copied()), path, is_dir)
+    }
+
+    #[test]
+    fn star_matches_inside_a_segment() {
+        assert!(glob_match("*.ts", "index.ts"));
+        assert!(glob_match("src/*.ts", "src/index.ts"));
+        assert!(!glob_match("*.ts", "src/index.ts"));
+        assert!(!glob_match("src/*.ts", "src/utils/index.ts"));
+    }
+
+    #[test]
+    fn double_star_matches_any_number_of_segments() {
+        assert!(glob_match("src/**", "src/index.ts"));
+        assert!(glob_match("src/**", "src/utils/index.ts"));
+        assert!(glob_match("src/**/*.ts", "src/index.ts"));
+        assert!(glob_match("src/**/*.ts", "src/utils/deep/index.ts"));
+        assert!(glob_match("**/test", "test"));
+        assert!(glob_match("**/test", "src/utils/test"));
+        assert!(!glob_match("src/**/*.ts", "lib/index.ts"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(glob_match("file?.js", "file1.js"));
+        assert!(!glob_match("file?.js", "file.js"));
+        assert!(!glob_match("a?b", "a/b"));
+    }
+
+    #[test]
+    fn anchored_patterns_match_from_the_root() {
+        assert!(is_pattern_matching(&["/dist"], "dist", true));
+        assert!(!is_pattern_matching(&["/dist"], "packages/dist", true));
+        assert!(is_pattern_matching(
+            &["src/generated"],
+            "src/generated",
+            true
+        ));
+        assert!(!is_pattern_matching(
+            &["src/generated"],
+            "lib/src/generated",
+            true
+        ));
+        assert!(is_pattern_matching(&["dist"], "packages/dist", true));
+    }
+
+    #[test]
+    fn dir_only_patterns_skip_files() {
+        assert!(is_pattern_matching(&["build/"], "build", true));
+        assert!(is_pattern_matching(&["build/"], "src/build", true));
+        assert!(!is_pattern_matching(&["build/"], "build", false));
+    }
+
+    #[test]
+    fn the_last_matching_pattern_decides() {
+        let lines = ["*.log", "!keep.log"];
+
+        assert!(is_pattern_matching(&lines, "error.log", false));
+        assert!(!is_pattern_matching(&lines, "keep.log", false));
+        assert!(is_pattern_matching(
+            &["!keep.log", "*.log"],
+            "keep.log",
+            false
+        ));
+        assert!(!is_pattern_matching(&lines, "index.ts", false));
+    }
+
+    #[test]
+    fn includes_and_excludes_apply_to_nested_paths() {
+        let root = create_temp_dir("include");
+
+        for include in ["src", "src/", "/src"] {
+            let filter = WatchFilter::new(&create_package(&root, &[include], &[]));
+
+            assert!(
+                filter.is_relevant(&root.join("src/index.ts")),
+                "{}",
+                include
+            );
+            assert!(filter.is_relevant(&root.join("src/utils/deep/index.ts")));
+            assert!(!filter.is_relevant(&root.join("lib/index.ts")));
+        }
+
+        let filter = WatchFilter::new(&create_package(&root, &["src", "!src/generated"], &[]));
+
+        assert!(filter.is_relevant(&root.join("src/index.ts")));
+        assert!(!filter.is_relevant(&root.join("src/generated/types.ts")));
+
+        let filter = WatchFilter::new(&create_package(&root, &[], &["src/generated", "*.md"]));
+
+        assert!(filter.is_relevant(&root.join("src/index.ts")));
+        assert!(!filter.is_relevant(&root.join("src/generated/types.ts")));
+        assert!(!filter.is_relevant(&root.join("docs/readme.md")));
+        assert!(!filter.is_relevant(&root.join("dist/index.js")));
+        assert!(!filter.is_relevant(&root.join("node_modules/lib/index.js")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn gitignore_files_apply_from_the_git_root_down() {
+        let git_root = create_temp_dir("gitignore");
+        let root = git_root.join("packages/app");
+
+        fs::create_dir_all(git_root.join(".git")).unwrap();
+        write_file(
+            &git_root.join(".gitignore"),
+            "*.log\n/packages/app/tmp\n/tmp\n",
+        );
+        write_file(&root.join(".gitignore"), "!keep.log\n");
+        write_file(&root.join("src/.gitignore"), "generated/\n");
+        fs::create_dir_all(root.join("src/generated")).unwrap();
+
+        let filter = WatchFilter::new(&create_package(&root, &[], &[]));
+
+        assert!(filter.is_relevant(&root.join("src/index.ts")));
+        assert!(filter.is_relevant(&root.join("keep.log")));
+        assert!(!filter.is_relevant(&root.join("error.log")));
+        assert!(!filter.is_relevant(&root.join("tmp/cache.js")));
+        assert!(!filter.is_relevant(&root.join("src/generated/types.ts")));
+        assert!(filter.is_relevant(&root.join("generated/types.ts")));
+
+        let _ = fs::remove_dir_all(&git_root);
+    }
+
+    #[test]
+    fn published_entry_files_watch_their_folder() {
+        let root = create_temp_dir("published");
+
+        write_file(
+            &root.join("package.json"),
+            r#"{"main": "lib/index.js", "types": "dist/index.d.ts", "files": ["lib", "README.md"]}"#,
+        );
+
+        let package = create_package(&root, &[], &[]);
+        let filter = WatchFilter::published(&package);
+
+        assert!(filter.is_relevant(&root.join("lib/index.js")));
+        assert!(filter.is_relevant(&root.join("lib/util.js")));
+        assert!(filter.is_relevant(&root.join("README.md")));
+        assert!(filter.is_relevant(&root.join("package.json")));
+        assert!(!filter.is_relevant(&root.join("lib/index.test.js")));
+        assert!(!filter.is_relevant(&root.join("scripts/release.js")));
+
+        write_file(&root.join("package.json"), r#"{"main": "index.js"}"#);
+
+        assert!(get_published_patterns(&package).is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
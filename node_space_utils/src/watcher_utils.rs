@@ -1,39 +1,62 @@
-use std::{path::Path, time::Duration};
-
-use notify::{EventKind, RecommendedWatcher, RecursiveMode};
-use notify_debouncer_full::{new_debouncer, DebouncedEvent, Debouncer, RecommendedCache};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-const DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(3);
+use notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer_opt, DebouncedEvent, Debouncer, RecommendedCache};
 
 type NotifyReciver = std::sync::mpsc::Receiver<Result<Vec<DebouncedEvent>, Vec<notify::Error>>>;
 type Watcher = Debouncer<RecommendedWatcher, RecommendedCache>;
 
 use crate::{
-    command_line::node_build::run_node_command,
+    cache::cached_build::run_cached_build,
     errors::{node_space::NodeSpaceError, watcher::WatcherError},
-    modals::socket_build_data::SocketBuildData,
-    watch_coordinator::coordinator_communication::send_data_to_coordinator,
+    modals::{content_hashes::ContentHashes, package::Package, socket_build_data::SocketBuildData},
+    watch_coordinator::coordinator_communication::{
+        attach_to_coordinator, send_data_to_coordinator,
+    },
+    watch_filter::WatchFilter,
 };
 
-pub fn extract_should_build_from_event(events: Vec<DebouncedEvent>) -> bool {
+/// the relevant paths of the events whose kind triggers a build of the package
+pub fn extract_changed_paths(
+    events: Vec<DebouncedEvent>,
+    package: &Package,
+    filter: &WatchFilter,
+) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+
     for event in events.iter() {
-        let should_build = match event.kind {
-            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => true,
-            _ => false,
-        };
+        if !package.is_triggered_by(&event.kind) {
+            continue;
+        }
 
-        if should_build {
-            return true;
+        for path in event.paths.iter() {
+            if filter.is_relevant(path) && !paths.contains(path) {
+                paths.push(path.clone());
+            }
         }
     }
 
-    false
+    paths
 }
 
-pub fn create_watcher_instance() -> Result<(NotifyReciver, Watcher), WatcherError> {
+pub fn create_watcher_instance(
+    debounce: Duration,
+) -> Result<(NotifyReciver, Watcher), WatcherError> {
     let (sender, reciver) = std::sync::mpsc::channel();
 
-    let current_watcher = match new_debouncer(DEBOUNCE_TIMEOUT, None, sender) {
+    // linked packages in node_modules point back into the workspace, following them can loop
+    let config = notify::Config::default().with_follow_symlinks(false);
+
+    let current_watcher = match new_debouncer_opt::<_, RecommendedWatcher, RecommendedCache>(
+        debounce,
+        None,
+        sender,
+        RecommendedCache::new(),
+        config,
+    ) {
         Ok(value) => value,
         Err(error) => return Err(WatcherError::CantCreateWatcher(error.to_string())),
     };
@@ -41,10 +64,10 @@ pub fn create_watcher_instance() -> Result<(NotifyReciver, Watcher), WatcherErro
     Ok((reciver, current_watcher))
 }
 
-pub fn create_watcher(path: &str) -> Result<(NotifyReciver, Watcher), WatcherError> {
-    let (reciver, mut current_watcher) = create_watcher_instance()?;
+pub fn create_watcher(package: &Package) -> Result<(NotifyReciver, Watcher), WatcherError> {
+    let (reciver, mut current_watcher) = create_watcher_instance(package.get_debounce())?;
 
-    match current_watcher.watch(Path::new(path), RecursiveMode::Recursive) {
+    match current_watcher.watch(Path::new(&package.path), RecursiveMode::Recursive) {
         Ok(_) => {}
         Err(error) => return Err(WatcherError::CantCreateWatcher(error.to_string())),
     };
@@ -52,7 +75,10 @@ pub fn create_watcher(path: &str) -> Result<(NotifyReciver, Watcher), WatcherErr
     Ok((reciver, current_watcher))
 }
 
-pub fn create_watcher_loop(path: &str, reciver: NotifyReciver) {
+pub fn create_watcher_loop(package: &Package, dependencies: &[Package], reciver: NotifyReciver) {
+    let filter = WatchFilter::new(package);
+    let mut content_hashes = ContentHashes::new();
+
     loop {
         let event_option = match reciver.recv() {
             Ok(value) => match value {
@@ -77,15 +103,23 @@ pub fn create_watcher_loop(path: &str, reciver: NotifyReciver) {
         }
 
         let events = event_option.unwrap();
-        let should_build = extract_should_build_from_event(events);
+        let changed_paths = extract_changed_paths(events, package, &filter);
 
-        if !should_build {
+        if changed_paths.is_empty() {
             continue;
         }
 
-        match run_node_command(path, "build") {
-            Ok(_) => (),
+        if !content_hashes.update(&changed_paths) {
+            println!("⏭️  contents unchanged, skipping build");
+
+            continue;
+        }
+
+        match run_cached_build(package, dependencies) {
+            Ok(_) => content_hashes.commit(),
             Err(error) => {
+                content_hashes.discard();
+
                 eprintln!("error building project: {}", error);
             }
         };
@@ -93,10 +127,10 @@ pub fn create_watcher_loop(path: &str, reciver: NotifyReciver) {
 }
 
 // TODO: recive update commands from coordinator
-pub fn add_watcher(path: &str) -> Result<(), WatcherError> {
-    let (reciver, _watcher) = create_watcher(path)?;
+pub fn add_watcher(package: &Package) -> Result<(), WatcherError> {
+    let (reciver, _watcher) = create_watcher(package)?;
 
-    create_watcher_loop(path, reciver);
+    create_watcher_loop(package, &[], reciver);
 
     Ok(())
 }
@@ -104,19 +138,25 @@ pub fn add_watcher(path: &str) -> Result<(), WatcherError> {
 pub fn add_local_watcher(data: SocketBuildData) -> Result<(), NodeSpaceError> {
     dbg!("add_local_watcher");
 
-    let path = data.project.path.clone();
-    let (reciver, _watcher) = create_watcher(&path)?;
+    let project = data.project.clone();
+    let dependencies = data.symlinks.clone();
+    let (reciver, _watcher) = create_watcher(&project)?;
 
     send_data_to_coordinator(data)?;
 
-    match run_node_command(&path, "build") {
-        Ok(_) => (),
-        Err(error) => {
-            eprintln!("error building project: {}", error);
-        }
-    };
+    // the coordinator drops the registration once this process exits
+    let _coordinator_session = attach_to_coordinator(&project.path)?;
+
+    if project.build_on_start.unwrap_or(true) {
+        match run_cached_build(&project, &dependencies) {
+            Ok(_) => (),
+            Err(error) => {
+                eprintln!("error building project: {}", error);
+            }
+        };
+    }
 
-    create_watcher_loop(&path, reciver);
+    create_watcher_loop(&project, &dependencies, reciver);
 
     Ok(())
 }